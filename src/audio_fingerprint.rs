@@ -12,7 +12,7 @@ impl AudioFingerPrint {
     pub async fn new(window: &Window) -> Option<Self> {
         Some(Self {
             hash: audio_hash().await,
-            check_audio_formats: CheckAudioFormats::new(&window),
+            check_audio_formats: CheckAudioFormats::new(window),
         })
     }
 }
@@ -44,7 +44,7 @@ pub async fn audio_hash() -> Option<f32> {
     let notify = Arc::new(Notify::new());
     let notify2 = notify.clone();
     let f = Closure::once_into_js(move |event: OfflineAudioCompletionEvent| {
-        if let Some(channel_data) = event.rendered_buffer().get_channel_data(0).ok() {
+        if let Ok(channel_data) = event.rendered_buffer().get_channel_data(0) {
             for sample in channel_data {
                 *hash_c.borrow_mut() += sample.abs();
             }
@@ -0,0 +1,210 @@
+use super::*;
+
+/// Globals that Selenium, PhantomJS, Nightmare and Playwright leave on `window` or `document`.
+/// Puppeteer doesn't leave any, see AUTOMATION_STACK_MARKERS and `BotSignals::devtools_protocol`.
+pub static AUTOMATION_GLOBALS: [&str; 16] = [
+    "__webdriver_evaluate",
+    "__selenium_evaluate",
+    "__webdriver_script_function",
+    "__webdriver_script_func",
+    "__webdriver_script_fn",
+    "__driver_evaluate",
+    "__driver_unwrapped",
+    "__selenium_unwrapped",
+    "_Selenium_IDE_Recorder",
+    "_selenium",
+    "callSelenium",
+    "callPhantom",
+    "_phantom",
+    "__nightmare",
+    "__playwright__binding__",
+    "__pwInitScripts",
+];
+
+/// ChromeDriver injects `cdc_<random>` properties, older versions prefix them with `$`.
+pub static AUTOMATION_PREFIXES: [&str; 3] = ["cdc_", "$cdc_", "$wdc_"];
+
+/// The source URLs Puppeteer gives the scripts it evaluates, the former before v16 and the latter since.
+/// They show up in the stack of any function such a script patched, i.e. a stealth plugin's `permissions.query`.
+pub static AUTOMATION_STACK_MARKERS: [&str; 2] = ["__puppeteer_evaluation_script__", "pptr:"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BotVerdict {
+    #[default]
+    Human,
+    Suspicious,
+    Bot,
+}
+
+/// The automation signals we check. `webdriver`, `automation_globals`, `automation_stack_markers`, `headless_user_agent`
+/// and `notification_permission_mismatch` are each enough on their own, the rest also happen on real devices.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BotSignals {
+    pub webdriver: bool,
+    pub automation_globals: Vec<String>,
+    /// AUTOMATION_STACK_MARKERS in the stack `navigator.permissions.query` calls a getter of its argument with.
+    pub automation_stack_markers: Vec<String>,
+    /// The console read the `stack` of an Error we logged, which it only does when a DevTools protocol client enabled
+    /// the Runtime domain. Puppeteer and Playwright always do, but so does an open devtools window.
+    pub devtools_protocol: bool,
+    pub headless_user_agent: bool,
+    /// A chromium UA without `window.chrome`, which old headless chrome didn't define.
+    /// Android WebViews don't define it either, so they never set this.
+    pub missing_window_chrome: bool,
+    /// No plugins on a desktop UA. Mobile browsers never have any.
+    pub no_plugins: bool,
+    /// The permissions API says notifications are denied while `Notification.permission` is still default, a headless chrome quirk.
+    pub notification_permission_mismatch: bool,
+    pub software_renderer: bool,
+    /// The screen is exactly as large as its available area, i.e. there's no taskbar or dock. Common in headless and VMs.
+    pub screen_equals_avail: bool,
+}
+
+impl BotSignals {
+    /// Takes the WebGL fingerprint we already collected, so we don't create another context just for the renderer.
+    pub async fn new(window: &Window, webgl: Option<&WebGLFingerPrint>) -> Option<Self> {
+        let navigator = window.navigator();
+        let user_agent = UserAgent::parse(&navigator.user_agent().ok()?);
+        let screen = ScreenFingerPrint::new(&window.screen().ok()?)?;
+
+        let mut automation_globals = Vec::new();
+        for target in [Some(JsValue::from(window)), get_path(window, &["document"])] {
+            let Some(target) = target.and_then(|t| t.dyn_into::<Object>().ok()) else {
+                continue;
+            };
+            automation_globals.extend(
+                Object::get_own_property_names(&target)
+                    .into_iter()
+                    .filter_map(|name| name.as_string())
+                    .filter(|name| {
+                        AUTOMATION_GLOBALS.contains(&name.as_str())
+                            || AUTOMATION_PREFIXES.iter().any(|p| name.starts_with(p))
+                    }),
+            );
+        }
+
+        let automation_stack_markers = permissions_query_stack(window)
+            .map(|stack| {
+                AUTOMATION_STACK_MARKERS
+                    .iter()
+                    .filter(|marker| stack.contains(*marker))
+                    .map(|marker| marker.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let chromium = user_agent.engine == Engine::Blink && !user_agent.webview;
+        let notification_permission_mismatch = match navigator.permissions() {
+            Ok(permissions) => {
+                query_permission(&permissions, "notifications").await
+                    == Some(PermissionState::Denied)
+                    && get_path(window, &["Notification", "permission"])
+                        .and_then(|p| p.as_string())
+                        .as_deref()
+                        == Some("default")
+            }
+            Err(_) => false,
+        };
+
+        Some(Self {
+            webdriver: get_path(&navigator, &["webdriver"])
+                .and_then(|w| w.as_bool())
+                .unwrap_or_default(),
+            automation_globals,
+            automation_stack_markers,
+            devtools_protocol: devtools_protocol(window),
+            headless_user_agent: user_agent.headless,
+            missing_window_chrome: chromium && get_path(window, &["chrome"]).is_none(),
+            no_plugins: user_agent.device == DeviceClass::Desktop
+                && navigator.plugins().map(|p| p.length() == 0).unwrap_or(true),
+            notification_permission_mismatch,
+            software_renderer: webgl
+                .and_then(|webgl| webgl.primary()?.gpu_identity())
+                .is_some_and(|gpu| gpu.software),
+            screen_equals_avail: screen.width == screen.avail_width
+                && screen.height == screen.avail_height,
+        })
+    }
+
+    /// Bot if any of the definite signals is set or three of the circumstantial ones are, Suspicious at two.
+    pub fn verdict(&self) -> BotVerdict {
+        if self.webdriver
+            || !self.automation_globals.is_empty()
+            || !self.automation_stack_markers.is_empty()
+            || self.headless_user_agent
+            || self.notification_permission_mismatch
+        {
+            return BotVerdict::Bot;
+        }
+        let circumstantial = [
+            self.devtools_protocol,
+            self.missing_window_chrome,
+            self.no_plugins,
+            self.software_renderer,
+            self.screen_equals_avail,
+        ]
+        .into_iter()
+        .filter(|s| *s)
+        .count();
+        match circumstantial {
+            0 | 1 => BotVerdict::Human,
+            2 => BotVerdict::Suspicious,
+            _ => BotVerdict::Bot,
+        }
+    }
+}
+
+/// Calls `navigator.permissions.query` with a descriptor whose `name` getter captures the stack. A native `query`
+/// reads it straight from our call, a patched one shows up in between. Error.stackTraceLimit is raised meanwhile,
+/// the wasm frames would push the patch out of the default 10.
+fn permissions_query_stack(window: &Window) -> Option<String> {
+    let permissions = get_path(window, &["navigator", "permissions"])?;
+    let error_constructor = get_path(window, &["Error"])?;
+    let stack = Rc::new(RefCell::new(None));
+    let getter = {
+        let stack = stack.clone();
+        Closure::<dyn Fn() -> JsValue>::new(move || {
+            *stack.borrow_mut() =
+                get_path(&js_sys::Error::new(""), &["stack"]).and_then(|s| s.as_string());
+            JsValue::from("notifications")
+        })
+    };
+    let descriptor = Object::new();
+    let property = Object::new();
+    Reflect::set(&property, &"get".into(), getter.as_ref()).ok()?;
+    Object::define_property(&descriptor, &"name".into(), &property);
+
+    let stack_trace_limit = Reflect::get(&error_constructor, &"stackTraceLimit".into()).ok()?;
+    _ = Reflect::set(&error_constructor, &"stackTraceLimit".into(), &64.into());
+    call_method(&permissions, "query", &Array::of1(&descriptor));
+    _ = Reflect::set(
+        &error_constructor,
+        &"stackTraceLimit".into(),
+        &stack_trace_limit,
+    );
+    stack.take()
+}
+
+/// Logs an Error with `console.debug`, the level browsers hide by default, and reports whether its `stack` getter ran.
+fn devtools_protocol(window: &Window) -> bool {
+    let Some(console) = get_path(window, &["console"]) else {
+        return false;
+    };
+    let read = Rc::new(RefCell::new(false));
+    let getter = {
+        let read = read.clone();
+        Closure::<dyn Fn() -> JsValue>::new(move || {
+            *read.borrow_mut() = true;
+            JsValue::from("")
+        })
+    };
+    let error = js_sys::Error::new("");
+    let property = Object::new();
+    if Reflect::set(&property, &"get".into(), getter.as_ref()).is_err() {
+        return false;
+    }
+    Object::define_property(&error, &"stack".into(), &property);
+    call_method(&console, "debug", &Array::of1(&error));
+    let read = *read.borrow();
+    read
+}
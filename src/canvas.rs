@@ -62,7 +62,7 @@ fn render_text_image(canvas: &HtmlCanvasElement, ctx: &CanvasRenderingContext2d)
     ctx.fill_text(printed_text, 2., 15.).unwrap();
     ctx.set_fill_style(&JsValue::from_str("rgba(102, 204, 0, 0.2)"));
     ctx.set_font("18pt Arial");
-    ctx.fill_text(&printed_text, 4., 45.).unwrap();
+    ctx.fill_text(printed_text, 4., 45.).unwrap();
 }
 
 fn supports_winding(ctx: &CanvasRenderingContext2d) -> bool {
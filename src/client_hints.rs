@@ -0,0 +1,99 @@
+use js_sys::Promise;
+
+use super::*;
+
+/// The high entropy hints we ask `getHighEntropyValues` for.
+pub static HIGH_ENTROPY_HINTS: [&str; 6] = [
+    "architecture",
+    "bitness",
+    "model",
+    "platformVersion",
+    "fullVersionList",
+    "wow64",
+];
+
+/// `navigator.userAgentData`, which only chromium based browsers implement.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ClientHintsFingerPrint {
+    pub brands: Vec<BrandVersion>,
+    pub mobile: bool,
+    pub platform: String,
+    pub architecture: Option<String>,
+    pub bitness: Option<String>,
+    pub model: Option<String>,
+    pub platform_version: Option<String>,
+    pub full_version_list: Vec<BrandVersion>,
+    pub wow64: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BrandVersion {
+    pub brand: String,
+    pub version: String,
+}
+
+impl ClientHintsFingerPrint {
+    /// Unsupported if there's no `navigator.userAgentData`.
+    /// The high entropy fields are None when `getHighEntropyValues` rejects, i.e. outside a secure context.
+    pub async fn new(navigator: &Navigator) -> Support<Self> {
+        Self::from_navigator(navigator).await.into()
+    }
+
+    async fn from_navigator(navigator: &Navigator) -> Option<Self> {
+        let data = Reflect::get(navigator, &"userAgentData".into()).ok()?;
+        if data.is_undefined() || data.is_null() {
+            return None;
+        }
+        let hints = HIGH_ENTROPY_HINTS
+            .iter()
+            .map(|hint| JsValue::from_str(hint))
+            .collect::<Array>();
+        let values = match call_method(&data, "getHighEntropyValues", &Array::of1(&hints))
+            .and_then(|p| p.dyn_into::<Promise>().ok())
+        {
+            Some(promise) => JsFuture::from(promise).await.unwrap_or(JsValue::UNDEFINED),
+            None => JsValue::UNDEFINED,
+        };
+        let get = |object: &JsValue, key: &str| Reflect::get(object, &key.into()).ok();
+        let string = |key: &str| get(&values, key).and_then(|v| v.as_string());
+
+        Some(Self {
+            brands: brand_versions(get(&data, "brands")),
+            mobile: get(&data, "mobile")
+                .and_then(|m| m.as_bool())
+                .unwrap_or_default(),
+            platform: get(&data, "platform")
+                .and_then(|p| p.as_string())
+                .unwrap_or_default(),
+            architecture: string("architecture"),
+            bitness: string("bitness"),
+            model: string("model"),
+            platform_version: string("platformVersion"),
+            full_version_list: brand_versions(get(&values, "fullVersionList")),
+            wow64: get(&values, "wow64").and_then(|w| w.as_bool()),
+        })
+    }
+}
+
+fn brand_versions(array: Option<JsValue>) -> Vec<BrandVersion> {
+    array
+        .and_then(|array| array.dyn_into::<Array>().ok())
+        .map(|array| {
+            array
+                .into_iter()
+                .map(|b| {
+                    let string = |key: &str| {
+                        Reflect::get(&b, &key.into())
+                            .ok()
+                            .and_then(|v| v.as_string())
+                            .unwrap_or_default()
+                    };
+                    BrandVersion {
+                        brand: string("brand"),
+                        version: string("version"),
+                    }
+                })
+                .collect::<Vec<BrandVersion>>()
+        })
+        .unwrap_or_default()
+}
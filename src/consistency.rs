@@ -0,0 +1,412 @@
+use super::*;
+
+/// Fonts that only ship with one desktop OS. Helvetica and the Office fonts are left out, fontconfig aliases
+/// the former on linux and Office installs the latter on macOS.
+pub static OS_FONTS: [(OsFamily, &[&str]); 3] = [
+    (
+        OsFamily::Windows,
+        &["Segoe UI", "Segoe Print", "Segoe Script"],
+    ),
+    (OsFamily::MacOs, &["Geneva", "Optima"]),
+    (OsFamily::Linux, &["Ubuntu"]),
+];
+
+/// The most logical cores a phone or tablet ships with.
+pub const MAX_MOBILE_HARDWARE_CONCURRENCY: f64 = 12.0;
+/// The most logical cores a Mac ships with, the 2019 Mac Pro has 56.
+pub const MAX_MAC_HARDWARE_CONCURRENCY: f64 = 64.0;
+/// `navigator.deviceMemory` is rounded and capped to this by the spec.
+pub const MAX_DEVICE_MEMORY: f64 = 8.0;
+
+/// The IANA time zone areas we expect for the region of a language tag.
+/// en-US is left out on purpose, it's the default UI language far outside the US.
+pub static REGION_TIME_ZONE_AREAS: [(&str, &[&str]); 24] = [
+    ("CA", &["America"]),
+    ("MX", &["America"]),
+    ("BR", &["America"]),
+    ("AR", &["America"]),
+    ("GB", &["Europe"]),
+    ("DE", &["Europe"]),
+    ("FR", &["Europe"]),
+    ("ES", &["Europe", "Atlantic", "Africa"]),
+    ("IT", &["Europe"]),
+    ("NL", &["Europe"]),
+    ("PL", &["Europe"]),
+    ("SE", &["Europe"]),
+    ("UA", &["Europe"]),
+    ("TR", &["Europe", "Asia"]),
+    ("RU", &["Europe", "Asia"]),
+    ("JP", &["Asia"]),
+    ("CN", &["Asia"]),
+    ("KR", &["Asia"]),
+    ("TW", &["Asia"]),
+    ("IN", &["Asia"]),
+    ("ID", &["Asia"]),
+    ("VN", &["Asia"]),
+    ("AU", &["Australia", "Antarctica"]),
+    ("NZ", &["Pacific"]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Severity {
+    /// Has innocent explanations, i.e. a VPN or a traveller.
+    #[default]
+    Low,
+    Medium,
+    /// Doesn't happen without spoofing.
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Inconsistency {
+    pub check: &'static str,
+    pub severity: Severity,
+    pub detail: String,
+}
+
+/// Everything `inconsistencies` reads, as plain strings and numbers. The browser sends this along with the fingerprint,
+/// and with the `serde` feature the server deserializes it and re-runs the checks.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConsistencySignals {
+    pub user_agent: String,
+    pub platform: String,
+    /// `navigator.userAgentData.platform`, None where client hints are unsupported.
+    pub client_hints_platform: Option<String>,
+    pub language: String,
+    pub max_touch_points: i32,
+    pub hardware_concurrency: f64,
+    pub device_memory: Option<f64>,
+    /// `FeatureDetectionFingerPrint::present`.
+    pub features_present: Vec<String>,
+    /// The unmasked renderer of the primary WebGL context.
+    pub webgl_renderer: Option<String>,
+    pub intl_locale: Option<String>,
+    pub time_zone: Option<String>,
+    /// See `PermissionFingerPrint::accepted`. None without the permissions API.
+    pub permissions: Option<Vec<String>>,
+    /// `navigator.connection.type`.
+    pub network_type: Option<String>,
+    pub fonts: Option<Vec<String>>,
+}
+
+impl FingerPrint {
+    /// Cross checks the collected signals against each other, see `ConsistencySignals::inconsistencies`.
+    pub fn inconsistencies(&self) -> Vec<Inconsistency> {
+        ConsistencySignals::new(self)
+            .map(|signals| signals.inconsistencies())
+            .unwrap_or_default()
+    }
+}
+
+impl ConsistencySignals {
+    /// Returns None without a window fingerprint.
+    pub fn new(finger_print: &FingerPrint) -> Option<Self> {
+        let window = finger_print.window_finger_print.as_ref()?;
+        let navigator = &window.navigator_finger_print;
+        let intl = window.intl_finger_print.as_ref();
+        Some(Self {
+            user_agent: navigator.user_agent.clone(),
+            platform: navigator.platform.clone(),
+            client_hints_platform: navigator
+                .client_hints
+                .supported()
+                .map(|client_hints| client_hints.platform.clone()),
+            language: navigator.language.clone(),
+            max_touch_points: navigator.max_touch_points,
+            hardware_concurrency: navigator.hardware_concurrency,
+            device_memory: navigator.device_memory,
+            features_present: window.feature_detection_finger_print.present.clone(),
+            webgl_renderer: finger_print
+                .webgl_finger_print
+                .as_ref()
+                .and_then(|webgl| webgl.primary()?.renderer.clone()),
+            intl_locale: intl.and_then(|intl| intl.locale.clone()),
+            time_zone: intl.and_then(|intl| intl.time_zone.clone()),
+            permissions: navigator.permission_fingerprint.as_ref().map(|p| {
+                p.accepted()
+                    .into_iter()
+                    .map(|name| name.to_string())
+                    .collect()
+            }),
+            network_type: navigator
+                .network_information
+                .as_ref()
+                .and_then(|n| n.type_.clone()),
+            fonts: window.fonts.clone(),
+        })
+    }
+
+    /// Pure rust, so a server can re-run it on a submitted fingerprint.
+    /// Checks whose inputs are missing are skipped. Sorted from most to least severe.
+    pub fn inconsistencies(&self) -> Vec<Inconsistency> {
+        let mut findings = Vec::new();
+        let user_agent = UserAgent::parse(&self.user_agent);
+        let features = FeatureDetectionFingerPrint {
+            present: self.features_present.clone(),
+        }
+        .detect();
+        let mut flag = |check, severity, detail: String| {
+            findings.push(Inconsistency {
+                check,
+                severity,
+                detail,
+            })
+        };
+
+        if let Some(platform_os) = platform_os(&self.platform) {
+            if !os_compatible(user_agent.os, platform_os) {
+                flag(
+                    "user_agent_platform",
+                    Severity::High,
+                    format!(
+                        "UA says {:?} but platform is {:?}",
+                        user_agent.os, self.platform
+                    ),
+                );
+            }
+        }
+
+        if let Some(client_hints_platform) = &self.client_hints_platform {
+            if let Some(hints_os) = client_hints_os(client_hints_platform) {
+                if !os_compatible(user_agent.os, hints_os) {
+                    flag(
+                        "user_agent_client_hints",
+                        Severity::High,
+                        format!(
+                            "UA says {:?} but client hints say {client_hints_platform:?}",
+                            user_agent.os
+                        ),
+                    );
+                }
+            }
+        }
+
+        if features.matches_user_agent(&user_agent) == Some(false) {
+            flag(
+                "user_agent_engine",
+                Severity::High,
+                format!(
+                    "UA says {:?} {:?} but features say {features}",
+                    user_agent.engine, user_agent.engine_version
+                ),
+            );
+        }
+
+        if let Some(gpu) = self.webgl_renderer.as_deref().map(GpuIdentity::parse) {
+            let apple_os = matches!(user_agent.os, OsFamily::MacOs | OsFamily::Ios);
+            if gpu.vendor == GpuVendor::Apple && !apple_os && user_agent.os != OsFamily::Other {
+                flag(
+                    "gpu_os",
+                    Severity::High,
+                    format!("Apple GPU on a {:?} UA", user_agent.os),
+                );
+            }
+            let direct3d = matches!(
+                gpu.backend,
+                GraphicsBackend::D3D9 | GraphicsBackend::D3D11 | GraphicsBackend::D3D12
+            );
+            if direct3d && user_agent.os != OsFamily::Windows && user_agent.os != OsFamily::Other {
+                flag(
+                    "gpu_os",
+                    Severity::High,
+                    format!("{:?} backend on a {:?} UA", gpu.backend, user_agent.os),
+                );
+            }
+            if gpu.backend == GraphicsBackend::Metal && !apple_os {
+                flag(
+                    "gpu_os",
+                    Severity::High,
+                    format!("Metal backend on a {:?} UA", user_agent.os),
+                );
+            }
+        }
+
+        let mobile = matches!(user_agent.device, DeviceClass::Mobile | DeviceClass::Tablet);
+        if mobile && self.max_touch_points == 0 {
+            flag(
+                "touch_points",
+                Severity::High,
+                format!("{:?} UA without touch points", user_agent.device),
+            );
+        }
+
+        let max_cores = if mobile {
+            Some(MAX_MOBILE_HARDWARE_CONCURRENCY)
+        } else if user_agent.os == OsFamily::MacOs {
+            Some(MAX_MAC_HARDWARE_CONCURRENCY)
+        } else {
+            None
+        };
+        if let Some(max_cores) = max_cores {
+            if self.hardware_concurrency > max_cores {
+                flag(
+                    "hardware_concurrency",
+                    Severity::Medium,
+                    format!(
+                        "{} cores on a {:?} {:?} UA",
+                        self.hardware_concurrency, user_agent.os, user_agent.device
+                    ),
+                );
+            }
+        }
+        if self
+            .device_memory
+            .is_some_and(|memory| memory > MAX_DEVICE_MEMORY)
+        {
+            flag(
+                "device_memory",
+                Severity::Medium,
+                format!(
+                    "deviceMemory {:?} is above the spec's cap",
+                    self.device_memory
+                ),
+            );
+        }
+
+        if let (Some(locale), Some(language)) = (
+            self.intl_locale.as_deref().and_then(primary_subtag),
+            primary_subtag(&self.language),
+        ) {
+            if !locale.eq_ignore_ascii_case(language) {
+                flag(
+                    "intl_locale_language",
+                    Severity::Low,
+                    format!(
+                        "Intl locale {:?} but language {:?}",
+                        self.intl_locale, self.language
+                    ),
+                );
+            }
+        }
+        if let Some(time_zone) = &self.time_zone {
+            if !time_zone_fits_language(time_zone, &self.language) {
+                flag(
+                    "time_zone_language",
+                    Severity::Low,
+                    format!("time zone {time_zone} for language {:?}", self.language),
+                );
+            }
+        }
+
+        if matches!(user_agent.engine, Engine::Gecko | Engine::WebKit) {
+            if let Some(permissions) = &self.permissions {
+                let blink_only = [
+                    "accelerometer",
+                    "magnetometer",
+                    "background-sync",
+                    "payment-handler",
+                ];
+                // Medium, browsers keep adding permission names and a new release may accept one of these.
+                if let Some(name) = blink_only
+                    .iter()
+                    .find(|name| permissions.iter().any(|p| p == *name))
+                {
+                    flag(
+                        "user_agent_permissions",
+                        Severity::Medium,
+                        format!(
+                            "{:?} UA accepts the Blink only permission {name}",
+                            user_agent.engine
+                        ),
+                    );
+                }
+            }
+        }
+
+        // Chrome only exposes connection.type on Android and ChromeOS.
+        let desktop_os = matches!(
+            user_agent.os,
+            OsFamily::Windows | OsFamily::MacOs | OsFamily::Linux
+        );
+        if let Some(type_) = self.network_type.as_ref().filter(|_| desktop_os) {
+            flag(
+                "network_type",
+                Severity::Medium,
+                format!("connection.type {type_:?} on a {:?} UA", user_agent.os),
+            );
+        }
+
+        if let Some(fonts) = self.fonts.as_ref().filter(|f| !f.is_empty()) {
+            for (os, markers) in OS_FONTS.iter() {
+                if os_compatible(user_agent.os, *os) || user_agent.os == OsFamily::Other {
+                    continue;
+                }
+                if let Some(font) = markers.iter().find(|m| fonts.iter().any(|f| f == *m)) {
+                    flag(
+                        "fonts_os",
+                        Severity::Medium,
+                        format!("{font} is a {os:?} font on a {:?} UA", user_agent.os),
+                    );
+                }
+            }
+        }
+
+        findings.sort_by_key(|i| std::cmp::Reverse(i.severity));
+        findings
+    }
+}
+
+/// The OS `navigator.platform` points at. Android and ChromeOS both report Linux.
+fn platform_os(platform: &str) -> Option<OsFamily> {
+    if platform.starts_with("Win") {
+        Some(OsFamily::Windows)
+    } else if platform.starts_with("Mac") {
+        Some(OsFamily::MacOs)
+    } else if ["iPhone", "iPad", "iPod"]
+        .iter()
+        .any(|p| platform.starts_with(p))
+    {
+        Some(OsFamily::Ios)
+    } else if platform.starts_with("Linux") || platform.starts_with("X11") {
+        Some(OsFamily::Linux)
+    } else {
+        None
+    }
+}
+
+fn client_hints_os(platform: &str) -> Option<OsFamily> {
+    match platform {
+        "Windows" => Some(OsFamily::Windows),
+        "macOS" => Some(OsFamily::MacOs),
+        "Linux" => Some(OsFamily::Linux),
+        "Android" => Some(OsFamily::Android),
+        "Chrome OS" | "Chromium OS" => Some(OsFamily::ChromeOs),
+        _ => None,
+    }
+}
+
+/// Whether a UA claiming `claimed` can report `reported` elsewhere.
+fn os_compatible(claimed: OsFamily, reported: OsFamily) -> bool {
+    claimed == reported
+        || claimed == OsFamily::Other
+        || matches!(
+            (claimed, reported),
+            (OsFamily::Android | OsFamily::ChromeOs, OsFamily::Linux)
+                // iPads ask for the desktop site by default and send a mac UA.
+                | (OsFamily::MacOs, OsFamily::Ios)
+                | (OsFamily::Ios, OsFamily::MacOs)
+        )
+}
+
+fn primary_subtag(tag: &str) -> Option<&str> {
+    tag.split(['-', '_']).next().filter(|s| !s.is_empty())
+}
+
+fn time_zone_fits_language(time_zone: &str, language: &str) -> bool {
+    let Some((area, _)) = time_zone.split_once('/') else {
+        return true;
+    };
+    if area == "Etc" {
+        return true;
+    }
+    let Some(region) = language.split(['-', '_']).nth(1) else {
+        return true;
+    };
+    REGION_TIME_ZONE_AREAS
+        .iter()
+        .find(|(r, _)| r.eq_ignore_ascii_case(region))
+        .is_none_or(|(_, areas)| areas.contains(&area))
+}
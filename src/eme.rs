@@ -0,0 +1,196 @@
+use js_sys::Promise;
+use web_sys::{
+    MediaKeySystemAccess, MediaKeySystemConfiguration, MediaKeySystemMediaCapability,
+    MediaKeysRequirement,
+};
+
+use super::*;
+
+/// The key systems we ask for, paired with the robustness levels to try for each of them.
+/// An empty robustness string asks for the key system's default. Levels are ordered weakest first,
+/// so once one is rejected the stronger ones are too.
+pub static KEY_SYSTEMS: [(&str, &[&str]); 4] = [
+    ("org.w3.clearkey", &[""]),
+    (
+        "com.widevine.alpha",
+        &[
+            "",
+            "SW_SECURE_CRYPTO",
+            "SW_SECURE_DECODE",
+            "HW_SECURE_DECODE",
+            "HW_SECURE_ALL",
+        ],
+    ),
+    ("com.microsoft.playready", &["", "150", "2000", "3000"]),
+    ("com.apple.fps", &[""]),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct EmeFingerPrint {
+    pub key_systems: Vec<KeySystemFingerPrint>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KeySystemFingerPrint {
+    pub key_system: String,
+    pub robustness: String,
+    pub supported: bool,
+    pub configuration: Option<KeySystemConfigurationFingerPrint>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KeySystemConfigurationFingerPrint {
+    pub label: Option<String>,
+    pub init_data_types: Vec<String>,
+    pub session_types: Vec<String>,
+    pub distinctive_identifier: Option<u32>,
+    pub persistent_state: Option<u32>,
+    pub audio_capabilities: Vec<KeySystemCapabilityFingerPrint>,
+    pub video_capabilities: Vec<KeySystemCapabilityFingerPrint>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct KeySystemCapabilityFingerPrint {
+    pub content_type: String,
+    pub robustness: String,
+}
+
+impl EmeFingerPrint {
+    /// Returns None if the browser doesn't expose EME, i.e. outside a secure context.
+    ///
+    /// Not invisible to the user: Firefox with DRM disabled shows its "play DRM-controlled content" bar
+    /// on the first Widevine request. We stop asking for a key system after its first NotSupportedError,
+    /// so the bar shows at most once per key system, and the remaining levels are recorded as unsupported.
+    pub async fn new(navigator: &Navigator) -> Option<Self> {
+        if !Reflect::has(navigator, &"requestMediaKeySystemAccess".into()).ok()? {
+            return None;
+        }
+        let mut key_systems = Vec::new();
+        for (key_system, robustness_levels) in KEY_SYSTEMS {
+            let mut rejected = false;
+            for robustness in robustness_levels {
+                let fingerprint = if rejected {
+                    KeySystemFingerPrint::unsupported(key_system, robustness)
+                } else {
+                    let (fingerprint, not_supported) =
+                        KeySystemFingerPrint::request(navigator, key_system, robustness).await;
+                    rejected = not_supported;
+                    fingerprint
+                };
+                key_systems.push(fingerprint);
+            }
+        }
+        Some(Self { key_systems })
+    }
+}
+
+impl KeySystemFingerPrint {
+    pub async fn new(navigator: &Navigator, key_system: &str, robustness: &str) -> Self {
+        Self::request(navigator, key_system, robustness).await.0
+    }
+
+    /// Also returns whether the request was rejected with a NotSupportedError.
+    async fn request(navigator: &Navigator, key_system: &str, robustness: &str) -> (Self, bool) {
+        let access = request_key_system_access(navigator, key_system, robustness).await;
+        let not_supported = access.as_ref().is_err_and(|error| {
+            Reflect::get(error, &"name".into())
+                .ok()
+                .and_then(|name| name.as_string())
+                .as_deref()
+                == Some("NotSupportedError")
+        });
+        let configuration = access
+            .ok()
+            .map(|access| KeySystemConfigurationFingerPrint::new(&access.get_configuration()));
+        let fingerprint = Self {
+            key_system: key_system.to_string(),
+            robustness: robustness.to_string(),
+            supported: configuration.is_some(),
+            configuration,
+        };
+        (fingerprint, not_supported)
+    }
+
+    fn unsupported(key_system: &str, robustness: &str) -> Self {
+        Self {
+            key_system: key_system.to_string(),
+            robustness: robustness.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl KeySystemConfigurationFingerPrint {
+    pub fn new(config: &MediaKeySystemConfiguration) -> Self {
+        Self {
+            label: config.get_label(),
+            init_data_types: strings(config.get_init_data_types()),
+            session_types: strings(config.get_session_types()),
+            distinctive_identifier: config.get_distinctive_identifier().map(|r| r as u32),
+            persistent_state: config.get_persistent_state().map(|r| r as u32),
+            audio_capabilities: capabilities(config.get_audio_capabilities()),
+            video_capabilities: capabilities(config.get_video_capabilities()),
+        }
+    }
+}
+
+async fn request_key_system_access(
+    navigator: &Navigator,
+    key_system: &str,
+    robustness: &str,
+) -> Result<MediaKeySystemAccess, JsValue> {
+    let video = MediaKeySystemMediaCapability::new();
+    video.set_content_type(r#"video/mp4; codecs="avc1.42E01E""#);
+    video.set_robustness(robustness);
+    let audio = MediaKeySystemMediaCapability::new();
+    audio.set_content_type(r#"audio/mp4; codecs="mp4a.40.2""#);
+
+    let config = MediaKeySystemConfiguration::new();
+    // The resolved configuration only keeps the init data types the CDM understands.
+    config.set_init_data_types(&Array::of5(
+        &"cenc".into(),
+        &"sinf".into(),
+        &"skd".into(),
+        &"keyids".into(),
+        &"webm".into(),
+    ));
+    config.set_video_capabilities(&Array::of1(&video));
+    config.set_audio_capabilities(&Array::of1(&audio));
+    // Asking for a distinctive identifier is what triggers the protected content prompt on android and firefox.
+    config.set_distinctive_identifier(MediaKeysRequirement::NotAllowed);
+    config.set_persistent_state(MediaKeysRequirement::Optional);
+
+    let promise: Promise =
+        navigator.request_media_key_system_access(key_system, &Array::of1(&config));
+    JsFuture::from(promise)
+        .await?
+        .dyn_into::<MediaKeySystemAccess>()
+}
+
+fn strings(array: Option<Array>) -> Vec<String> {
+    array
+        .map(|array| {
+            array
+                .into_iter()
+                .map(|s| s.as_string().unwrap_or_default())
+                .collect::<Vec<String>>()
+        })
+        .unwrap_or_default()
+}
+
+fn capabilities(array: Option<Array>) -> Vec<KeySystemCapabilityFingerPrint> {
+    array
+        .map(|array| {
+            array
+                .into_iter()
+                .map(|c| {
+                    let c = c.unchecked_into::<MediaKeySystemMediaCapability>();
+                    KeySystemCapabilityFingerPrint {
+                        content_type: c.get_content_type().unwrap_or_default(),
+                        robustness: c.get_robustness().unwrap_or_default(),
+                    }
+                })
+                .collect::<Vec<KeySystemCapabilityFingerPrint>>()
+        })
+        .unwrap_or_default()
+}
@@ -0,0 +1,363 @@
+use std::fmt;
+
+use super::*;
+
+/// A feature test, either a property path from the global object or a `CSS.supports` condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Probe {
+    Global(&'static [&'static str]),
+    Css(&'static str),
+}
+
+/// An engine release. Blink and Gecko only use the major, i.e. Chrome 120, WebKit uses the Safari version, i.e. 17.4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct EngineVersion {
+    pub major: u32,
+    pub minor: u32,
+}
+
+/// The release each engine shipped a feature in, None if it hadn't when this table was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FeatureRelease {
+    pub probe: Probe,
+    pub blink: Option<u32>,
+    pub gecko: Option<u32>,
+    pub webkit: Option<EngineVersion>,
+}
+
+/// Features only a single engine has, used to decide the engine before looking at versions.
+pub static ENGINE_MARKERS: [(Engine, Probe); 9] = [
+    (Engine::Blink, Probe::Css("-webkit-app-region: drag")),
+    (Engine::Blink, Probe::Global(&["webkitRequestFileSystem"])),
+    (
+        Engine::Blink,
+        Probe::Global(&["navigator", "userAgentData"]),
+    ),
+    (Engine::Gecko, Probe::Css("-moz-appearance: none")),
+    (Engine::Gecko, Probe::Global(&["mozInnerScreenX"])),
+    (Engine::Gecko, Probe::Global(&["InstallTrigger"])),
+    (Engine::WebKit, Probe::Css("-apple-pay-button-style: black")),
+    (Engine::WebKit, Probe::Global(&["GestureEvent"])),
+    (Engine::WebKit, Probe::Global(&["ApplePaySession"])),
+];
+
+const fn release(
+    probe: Probe,
+    blink: Option<u32>,
+    gecko: Option<u32>,
+    webkit: Option<(u32, u32)>,
+) -> FeatureRelease {
+    FeatureRelease {
+        probe,
+        blink,
+        gecko,
+        webkit: match webkit {
+            Some((major, minor)) => Some(EngineVersion { major, minor }),
+            None => None,
+        },
+    }
+}
+
+/// Features with a known release in every engine, ordered roughly by age.
+pub static FEATURE_RELEASES: [FeatureRelease; 22] = [
+    release(
+        Probe::Global(&["Array", "prototype", "at"]),
+        Some(92),
+        Some(90),
+        Some((15, 4)),
+    ),
+    release(
+        Probe::Global(&["Object", "hasOwn"]),
+        Some(93),
+        Some(92),
+        Some((15, 4)),
+    ),
+    release(
+        Probe::Global(&["Array", "prototype", "findLast"]),
+        Some(97),
+        Some(104),
+        Some((15, 4)),
+    ),
+    release(
+        Probe::Global(&["structuredClone"]),
+        Some(98),
+        Some(94),
+        Some((15, 4)),
+    ),
+    release(
+        Probe::Css("selector(:has(a))"),
+        Some(105),
+        Some(121),
+        Some((15, 4)),
+    ),
+    release(
+        Probe::Global(&["Array", "prototype", "toSorted"]),
+        Some(110),
+        Some(115),
+        Some((16, 0)),
+    ),
+    release(
+        Probe::Global(&["String", "prototype", "isWellFormed"]),
+        Some(111),
+        Some(119),
+        Some((16, 4)),
+    ),
+    release(
+        Probe::Css("text-wrap: balance"),
+        Some(114),
+        Some(121),
+        Some((17, 5)),
+    ),
+    release(
+        Probe::Global(&["ArrayBuffer", "prototype", "transfer"]),
+        Some(114),
+        Some(122),
+        Some((17, 4)),
+    ),
+    release(
+        Probe::Global(&["Object", "groupBy"]),
+        Some(117),
+        Some(119),
+        Some((17, 4)),
+    ),
+    release(
+        Probe::Global(&["Promise", "withResolvers"]),
+        Some(119),
+        Some(121),
+        Some((17, 4)),
+    ),
+    release(
+        Probe::Global(&["URL", "canParse"]),
+        Some(120),
+        Some(115),
+        Some((17, 0)),
+    ),
+    release(
+        Probe::Global(&["Array", "fromAsync"]),
+        Some(121),
+        Some(115),
+        Some((16, 4)),
+    ),
+    release(
+        Probe::Global(&["Set", "prototype", "union"]),
+        Some(122),
+        Some(127),
+        Some((17, 0)),
+    ),
+    release(
+        Probe::Global(&["Iterator", "from"]),
+        Some(122),
+        Some(131),
+        Some((18, 4)),
+    ),
+    release(Probe::Css("field-sizing: content"), Some(123), None, None),
+    release(
+        Probe::Global(&["URL", "parse"]),
+        Some(126),
+        Some(126),
+        Some((18, 0)),
+    ),
+    release(
+        Probe::Global(&["Promise", "try"]),
+        Some(128),
+        Some(134),
+        Some((18, 2)),
+    ),
+    release(
+        Probe::Global(&["Intl", "DurationFormat"]),
+        Some(129),
+        Some(136),
+        Some((16, 4)),
+    ),
+    release(
+        Probe::Global(&["Float16Array"]),
+        Some(135),
+        Some(129),
+        Some((18, 2)),
+    ),
+    release(
+        Probe::Global(&["RegExp", "escape"]),
+        Some(136),
+        Some(134),
+        Some((18, 2)),
+    ),
+    release(
+        Probe::Global(&["Uint8Array", "fromBase64"]),
+        Some(140),
+        Some(133),
+        Some((18, 2)),
+    ),
+];
+
+/// The outcome of every probe in ENGINE_MARKERS and FEATURE_RELEASES, by `Probe::name`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FeatureDetectionFingerPrint {
+    pub present: Vec<String>,
+}
+
+/// The engine and version range the features point at, independent of what the UA says.
+/// `min_version` is inclusive and `max_version` exclusive, so Blink 120..124 means "Blink >= 120, < 124".
+/// A min at or above the max means the features contradict each other, i.e. a polyfill or a feature turned off by flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct EngineDetection {
+    pub engine: Engine,
+    pub min_version: Option<EngineVersion>,
+    pub max_version: Option<EngineVersion>,
+}
+
+impl Probe {
+    pub fn name(&self) -> String {
+        match self {
+            Probe::Global(path) => path.join("."),
+            Probe::Css(condition) => format!("CSS.supports({condition})"),
+        }
+    }
+
+    fn test(&self) -> bool {
+        let global = js_sys::global();
+        match self {
+            Probe::Global(path) => get_path(&global, path).is_some(),
+            Probe::Css(condition) => Reflect::get(&global, &"CSS".into())
+                .ok()
+                .and_then(|css| call_method(&css, "supports", &Array::of1(&(*condition).into())))
+                .and_then(|supported| supported.as_bool())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FeatureDetectionFingerPrint {
+    pub fn new() -> Self {
+        let present = ENGINE_MARKERS
+            .iter()
+            .map(|(_, probe)| probe)
+            .chain(FEATURE_RELEASES.iter().map(|r| &r.probe))
+            .filter(|probe| probe.test())
+            .map(|probe| probe.name())
+            .collect::<Vec<String>>();
+        Self { present }
+    }
+
+    fn has(&self, probe: &Probe) -> bool {
+        let name = probe.name();
+        self.present.contains(&name)
+    }
+
+    /// The engine with the most markers present, and the versions that fit the present and missing features.
+    pub fn detect(&self) -> EngineDetection {
+        let markers = |engine: Engine| {
+            ENGINE_MARKERS
+                .iter()
+                .filter(|(e, probe)| *e == engine && self.has(probe))
+                .count()
+        };
+        let mut counts =
+            [Engine::Blink, Engine::Gecko, Engine::WebKit].map(|engine| (engine, markers(engine)));
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let engine = match counts {
+            [(engine, first), (_, second), _] if first > second => engine,
+            _ => return EngineDetection::default(),
+        };
+
+        let mut min_version = None;
+        let mut max_version = None;
+        for feature in FEATURE_RELEASES.iter() {
+            let Some(released) = feature.release(engine) else {
+                continue;
+            };
+            if self.has(&feature.probe) {
+                min_version = min_version.max(Some(released));
+            } else if max_version.is_none_or(|max| released < max) {
+                max_version = Some(released);
+            }
+        }
+        EngineDetection {
+            engine,
+            min_version,
+            max_version,
+        }
+    }
+}
+
+impl FeatureRelease {
+    pub fn release(&self, engine: Engine) -> Option<EngineVersion> {
+        let major = |major: u32| EngineVersion { major, minor: 0 };
+        match engine {
+            Engine::Blink => self.blink.map(major),
+            Engine::Gecko => self.gecko.map(major),
+            Engine::WebKit => self.webkit,
+            _ => None,
+        }
+    }
+}
+
+impl EngineVersion {
+    /// Reads the leading major.minor of a dotted version string.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next().and_then(|m| m.parse().ok()).unwrap_or(0);
+        Some(Self { major, minor })
+    }
+}
+
+impl EngineDetection {
+    /// Whether the UA claims the engine we detected, and a version inside the range.
+    /// None if we couldn't tell the engine, or the UA is one we can't compare, i.e. Trident.
+    pub fn matches_user_agent(&self, user_agent: &UserAgent) -> Option<bool> {
+        if self.engine == Engine::Unknown {
+            return None;
+        }
+        if user_agent.engine != self.engine {
+            return Some(false);
+        }
+        // AppleWebKit/605.1.15 is frozen, the Safari or iOS version is what tracks WebKit.
+        let claimed = match self.engine {
+            Engine::WebKit if user_agent.browser == BrowserFamily::Safari => {
+                user_agent.browser_version.as_deref()
+            }
+            Engine::WebKit if user_agent.os == OsFamily::Ios => user_agent.os_version.as_deref(),
+            Engine::WebKit => None,
+            _ => user_agent.engine_version.as_deref(),
+        };
+        let Some(claimed) = claimed.and_then(EngineVersion::parse) else {
+            return Some(true);
+        };
+        let claimed = match self.engine {
+            Engine::WebKit => claimed,
+            _ => EngineVersion {
+                major: claimed.major,
+                minor: 0,
+            },
+        };
+        Some(
+            self.min_version.is_none_or(|min| claimed >= min)
+                && self.max_version.is_none_or(|max| claimed < max),
+        )
+    }
+}
+
+impl fmt::Display for EngineVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.minor == 0 {
+            write!(f, "{}", self.major)
+        } else {
+            write!(f, "{}.{}", self.major, self.minor)
+        }
+    }
+}
+
+impl fmt::Display for EngineDetection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.engine)?;
+        let mut separator = " ";
+        if let Some(min) = self.min_version {
+            write!(f, "{separator}>= {min}")?;
+            separator = ", ";
+        }
+        if let Some(max) = self.max_version {
+            write!(f, "{separator}< {max}")?;
+        }
+        Ok(())
+    }
+}
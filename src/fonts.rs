@@ -1,7 +1,7 @@
 use super::*;
 use web_sys::{Document, HtmlSpanElement};
 
-pub static FONT_LIST: [&'static str; 89] = [
+pub static FONT_LIST: [&str; 89] = [
     "Arial",
     "Arial Black",
     "Arial Narrow",
@@ -140,3 +140,16 @@ pub fn detect_fonts(document: &Document) -> Option<Vec<bool>> {
     }
     Some(detect_font)
 }
+
+/// The names from FONT_LIST that detect_fonts found.
+pub fn detect_font_names(document: &Document) -> Option<Vec<String>> {
+    let detected = detect_fonts(document)?;
+    Some(
+        FONT_LIST
+            .iter()
+            .zip(detected.chunks(3))
+            .filter(|(_, matched)| matched.iter().any(|m| *m))
+            .map(|(font, _)| font.to_string())
+            .collect(),
+    )
+}
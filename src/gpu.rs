@@ -0,0 +1,326 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+    Apple,
+    Qualcomm,
+    Arm,
+    ImgTec,
+    Microsoft,
+    Google,
+    Mesa,
+    #[default]
+    Unknown,
+}
+
+/// The graphics API the browser renders WebGL with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum GraphicsBackend {
+    D3D9,
+    D3D11,
+    D3D12,
+    Metal,
+    OpenGL,
+    OpenGLES,
+    Vulkan,
+    #[default]
+    Unknown,
+}
+
+/// Renderer names of the CPU rasterizers browsers fall back to without a usable GPU.
+pub static SOFTWARE_RENDERERS: [&str; 5] = [
+    "swiftshader",
+    "llvmpipe",
+    "softpipe",
+    "lavapipe",
+    "basic render driver",
+];
+
+/// A normalised view of a WebGL renderer string.
+///
+/// `model` is the device name without the vendor prefix, i.e. "GeForce RTX 3070",
+/// and `model_family` replaces the model number with its series, i.e. "GeForce RTX 3000".
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GpuIdentity {
+    pub vendor: GpuVendor,
+    pub model_family: Option<String>,
+    pub model: Option<String>,
+    pub backend: GraphicsBackend,
+    pub angle: bool,
+    pub software: bool,
+}
+
+impl GpuIdentity {
+    /// Parses an `UNMASKED_RENDERER_WEBGL` or `RENDERER` string. Never fails, anything we don't recognise is Unknown.
+    pub fn parse(renderer: &str) -> Self {
+        let renderer = renderer.trim();
+        let lower = renderer.to_ascii_lowercase();
+        let angle = lower.starts_with("angle (");
+        let software = SOFTWARE_RENDERERS.iter().any(|s| lower.contains(s));
+        let vendor = vendor(&lower);
+        let backend = backend(&lower);
+
+        let device = if angle {
+            // ANGLE (vendor, device, backend), or ANGLE (device) on older chrome.
+            let inner = renderer["angle (".len()..]
+                .strip_suffix(')')
+                .unwrap_or(&renderer["angle (".len()..]);
+            let parts = split_top_level(inner);
+            match parts.as_slice() {
+                [_, device, ..] => device.to_string(),
+                _ => inner.to_string(),
+            }
+        } else {
+            renderer.to_string()
+        };
+        let model = if software {
+            software_model(&lower)
+        } else {
+            clean_model(&device, vendor)
+        };
+        let model_family = model.as_deref().map(|m| model_family(m, vendor));
+
+        Self {
+            vendor,
+            model_family,
+            model,
+            backend,
+            angle,
+            software,
+        }
+    }
+
+    /// Checks the identity against the limits the same context reported.
+    /// Returns a description of every limit that doesn't fit the claimed GPU, so an empty Vec means plausible.
+    pub fn check_plausibility(&self, parameters: &WebGLParametersFingerPrint) -> Vec<&'static str> {
+        let mut issues = Vec::new();
+        let max_texture_size = parameters.max_texture_size;
+        if max_texture_size <= 0 || (max_texture_size & (max_texture_size - 1)) != 0 {
+            issues.push("max_texture_size is not a power of two");
+        }
+        if self.software {
+            if max_texture_size > 16384 {
+                issues.push("software renderer with a hardware sized max_texture_size");
+            }
+        } else {
+            match self.vendor {
+                GpuVendor::Nvidia | GpuVendor::Amd if max_texture_size < 16384 => {
+                    issues.push("discrete desktop GPU with max_texture_size below 16384");
+                }
+                GpuVendor::Intel | GpuVendor::Apple if max_texture_size < 8192 => {
+                    issues.push("desktop GPU with max_texture_size below 8192");
+                }
+                GpuVendor::Qualcomm | GpuVendor::Arm | GpuVendor::ImgTec
+                    if max_texture_size > 16384 =>
+                {
+                    issues.push("mobile GPU with max_texture_size above 16384");
+                }
+                _ => {}
+            }
+        }
+        if self.backend == GraphicsBackend::D3D11 && max_texture_size < 16384 {
+            issues.push("D3D11 backend with max_texture_size below 16384");
+        }
+        if matches!(
+            self.backend,
+            GraphicsBackend::D3D9 | GraphicsBackend::D3D11 | GraphicsBackend::D3D12
+        ) && self.vendor == GpuVendor::Apple
+        {
+            issues.push("Apple GPU on a Direct3D backend");
+        }
+        if self.backend == GraphicsBackend::Metal
+            && matches!(
+                self.vendor,
+                GpuVendor::Qualcomm | GpuVendor::Arm | GpuVendor::Microsoft
+            )
+        {
+            issues.push("non Apple mobile GPU on a Metal backend");
+        }
+        issues
+    }
+}
+
+impl WebGLContextFingerPrint {
+    pub fn gpu_identity(&self) -> Option<GpuIdentity> {
+        self.renderer.as_deref().map(GpuIdentity::parse)
+    }
+}
+
+fn vendor(lower: &str) -> GpuVendor {
+    let words = lower
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .collect::<Vec<&str>>();
+    let has_word = |w: &str| words.contains(&w);
+    if lower.contains("swiftshader") {
+        GpuVendor::Google
+    } else if lower.contains("basic render driver") {
+        GpuVendor::Microsoft
+    } else if ["nvidia", "geforce", "quadro", "tesla"]
+        .iter()
+        .any(|s| lower.contains(s))
+    {
+        GpuVendor::Nvidia
+    } else if lower.contains("radeon") || has_word("amd") || has_word("ati") {
+        GpuVendor::Amd
+    } else if lower.contains("intel") || lower.contains("iris") {
+        GpuVendor::Intel
+    } else if lower.contains("apple") {
+        GpuVendor::Apple
+    } else if lower.contains("adreno") || lower.contains("qualcomm") {
+        GpuVendor::Qualcomm
+    } else if lower.contains("mali") || has_word("arm") {
+        GpuVendor::Arm
+    } else if lower.contains("powervr") || lower.contains("imagination") {
+        GpuVendor::ImgTec
+    } else if ["llvmpipe", "softpipe", "lavapipe", "mesa"]
+        .iter()
+        .any(|s| lower.contains(s))
+    {
+        GpuVendor::Mesa
+    } else {
+        GpuVendor::Unknown
+    }
+}
+
+fn backend(lower: &str) -> GraphicsBackend {
+    if lower.contains("direct3d9") || lower.contains("d3d9") {
+        GraphicsBackend::D3D9
+    } else if lower.contains("direct3d11") || lower.contains("d3d11") {
+        GraphicsBackend::D3D11
+    } else if lower.contains("direct3d12") || lower.contains("d3d12") {
+        GraphicsBackend::D3D12
+    } else if lower.contains("metal") {
+        GraphicsBackend::Metal
+    } else if lower.contains("vulkan") {
+        GraphicsBackend::Vulkan
+    } else if lower.contains("opengl es") {
+        GraphicsBackend::OpenGLES
+    } else if lower.contains("opengl") {
+        GraphicsBackend::OpenGL
+    } else {
+        GraphicsBackend::Unknown
+    }
+}
+
+/// Splits on commas that aren't inside parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[start..].trim());
+    parts
+}
+
+fn software_model(lower: &str) -> Option<String> {
+    SOFTWARE_RENDERERS
+        .iter()
+        .find(|s| lower.contains(*s))
+        .map(|s| match *s {
+            "swiftshader" => "SwiftShader".to_string(),
+            "basic render driver" => "Basic Render Driver".to_string(),
+            s => s.to_string(),
+        })
+}
+
+fn clean_model(device: &str, vendor: GpuVendor) -> Option<String> {
+    let mut device = device.to_string();
+    // Vulkan 1.3.242 (NVIDIA GeForce RTX 3060 (0x00002503))
+    if device.starts_with("Vulkan") {
+        if let (Some(open), Some(close)) = (device.find('('), device.rfind(')')) {
+            device = device[open + 1..close].to_string();
+        }
+    }
+    for noise in ["(TM)", "(tm)", "(R)", "(r)", "ANGLE Metal Renderer:"] {
+        device = device.replace(noise, " ");
+    }
+    // drop everything from the first D3D shader model, driver suffix or parenthesised detail.
+    for cut in [
+        " Direct3D",
+        "/PCIe",
+        "/PCI",
+        "/SSE2",
+        " (",
+        ", ",
+        " OpenGL Engine",
+        " vs_",
+    ] {
+        if let Some(i) = device.find(cut) {
+            device.truncate(i);
+        }
+    }
+    let mut words = device.split_whitespace().collect::<Vec<&str>>();
+    while let Some(first) = words.first() {
+        let first = first.to_ascii_lowercase();
+        let is_vendor_prefix = matches!(
+            first.as_str(),
+            "mesa" | "nvidia" | "amd" | "ati" | "intel" | "apple" | "qualcomm" | "arm"
+        );
+        if !is_vendor_prefix || words.len() == 1 {
+            break;
+        }
+        words.remove(0);
+    }
+    let model = words.join(" ");
+    if model.is_empty()
+        || (vendor == GpuVendor::Apple && model == "GPU")
+        || vendor == GpuVendor::Unknown
+    {
+        return None;
+    }
+    Some(model)
+}
+
+fn model_family(model: &str, vendor: GpuVendor) -> String {
+    let words = model.split_whitespace().collect::<Vec<&str>>();
+    match vendor {
+        GpuVendor::Apple => words.first().copied().unwrap_or(model).to_string(),
+        GpuVendor::Arm => {
+            // Mali-G78 MP24 -> Mali-G
+            let first = words.first().copied().unwrap_or(model);
+            first
+                .trim_end_matches(|c: char| c.is_ascii_digit())
+                .to_string()
+        }
+        _ => {
+            let mut family = Vec::new();
+            for word in words {
+                let digits = word.chars().take_while(|c| c.is_ascii_digit()).count();
+                if digits == 0 {
+                    if word.chars().any(|c| c.is_ascii_digit()) {
+                        // Arc A770, Mali-G78, skip the model number
+                        break;
+                    }
+                    family.push(word.to_string());
+                    continue;
+                }
+                // NVIDIA names its series by the first two digits of a four digit number, everyone else by the first.
+                let keep = if vendor == GpuVendor::Nvidia && digits == 4 {
+                    2
+                } else {
+                    1
+                };
+                family.push(format!("{}{}", &word[..keep], "0".repeat(digits - keep)));
+                break;
+            }
+            if family.is_empty() {
+                model.to_string()
+            } else {
+                family.join(" ")
+            }
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use std::hash::{Hash, Hasher};
+
+use js_sys::Date;
+
+use super::*;
+
+/// A fixed instant, 2023-11-14T22:13:20Z, so formatted dates only differ by locale and time zone.
+pub const FIXED_TIMESTAMP_MS: f64 = 1_700_000_000_000.0;
+
+/// The keys `Intl.supportedValuesOf` accepts.
+pub static SUPPORTED_VALUES_KEYS: [&str; 6] = [
+    "calendar",
+    "collation",
+    "currency",
+    "numberingSystem",
+    "timeZone",
+    "unit",
+];
+
+/// The `Intl` classes with a `supportedLocalesOf`.
+pub static INTL_CLASSES: [&str; 8] = [
+    "Collator",
+    "DateTimeFormat",
+    "DisplayNames",
+    "ListFormat",
+    "NumberFormat",
+    "PluralRules",
+    "RelativeTimeFormat",
+    "Segmenter",
+];
+
+/// Locales from widely to barely supported, small-icu builds drop the tail of this list.
+pub static PROBE_LOCALES: [&str; 24] = [
+    "en-US",
+    "en-GB",
+    "de-DE",
+    "fr-FR",
+    "es-ES",
+    "pt-BR",
+    "ru-RU",
+    "ja-JP",
+    "zh-CN",
+    "zh-TW",
+    "ko-KR",
+    "ar-SA",
+    "he-IL",
+    "hi-IN",
+    "th-TH",
+    "tr-TR",
+    "fa-IR",
+    "bn-BD",
+    "sw-KE",
+    "am-ET",
+    "yue-HK",
+    "haw-US",
+    "chr-US",
+    "ff-Adlm-SN",
+];
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IntlFingerPrint {
+    pub time_zone: Option<String>,
+    pub calendar: Option<String>,
+    pub numbering_system: Option<String>,
+    pub locale: Option<String>,
+    pub hour_cycle: Option<String>,
+    /// `getTimezoneOffset` in January and July, they only differ where the time zone has DST.
+    pub january_offset: f64,
+    pub july_offset: f64,
+    pub number_format: Option<String>,
+    pub currency_format: Option<String>,
+    pub compact_format: Option<String>,
+    pub date_time_format: Option<String>,
+    pub relative_time_format: Option<String>,
+    pub list_format: Option<String>,
+}
+
+impl IntlFingerPrint {
+    /// Returns None if there's no `Intl.DateTimeFormat`, the formatter outputs are None where their `Intl` class is missing.
+    pub fn new() -> Option<Self> {
+        let date_time_format = construct("DateTimeFormat", &Object::new())?;
+        let resolved = call_method(&date_time_format, "resolvedOptions", &Array::new())?;
+        let option = |key: &str| {
+            Reflect::get(&resolved, &key.into())
+                .ok()
+                .and_then(|v| v.as_string())
+        };
+        let date = Date::new(&FIXED_TIMESTAMP_MS.into());
+
+        Some(Self {
+            time_zone: option("timeZone"),
+            calendar: option("calendar"),
+            numbering_system: option("numberingSystem"),
+            locale: option("locale"),
+            hour_cycle: option("hourCycle"),
+            january_offset: Date::new_with_year_month_day(2024, 0, 1).get_timezone_offset(),
+            july_offset: Date::new_with_year_month_day(2024, 6, 1).get_timezone_offset(),
+            number_format: format("NumberFormat", &[], &1234567.891.into()),
+            currency_format: format(
+                "NumberFormat",
+                &[("style", "currency"), ("currency", "EUR")],
+                &(-1234.5).into(),
+            ),
+            compact_format: format(
+                "NumberFormat",
+                &[("notation", "compact"), ("compactDisplay", "long")],
+                &1234567.0.into(),
+            ),
+            date_time_format: format(
+                "DateTimeFormat",
+                &[("dateStyle", "full"), ("timeStyle", "long")],
+                &date,
+            ),
+            relative_time_format: construct("RelativeTimeFormat", &options(&[("numeric", "auto")]))
+                .and_then(|formatter| {
+                    call_method(
+                        &formatter,
+                        "format",
+                        &Array::of2(&(-1.0).into(), &"day".into()),
+                    )
+                })
+                .and_then(|s| s.as_string()),
+            list_format: format(
+                "ListFormat",
+                &[("type", "conjunction")],
+                &Array::of3(&"a".into(), &"b".into(), &"c".into()),
+            ),
+        })
+    }
+}
+
+fn options(options: &[(&str, &str)]) -> Object {
+    let object = Object::new();
+    for (key, value) in options {
+        _ = Reflect::set(&object, &(*key).into(), &(*value).into());
+    }
+    object
+}
+
+/// `new Intl[name](undefined, options)`, so the formatter uses the browser's default locale.
+fn construct(name: &str, options: &Object) -> Option<JsValue> {
+    let intl = Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+    let constructor = Reflect::get(&intl, &name.into())
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    Reflect::construct(&constructor, &Array::of2(&JsValue::UNDEFINED, options)).ok()
+}
+
+fn format(name: &str, format_options: &[(&str, &str)], value: &JsValue) -> Option<String> {
+    let formatter = construct(name, &options(format_options))?;
+    call_method(&formatter, "format", &Array::of1(value))?.as_string()
+}
+
+/// What the browser's ICU build supports.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct IntlSupportFingerPrint {
+    /// One entry per SUPPORTED_VALUES_KEYS, empty where `Intl.supportedValuesOf` isn't implemented.
+    pub supported_values: Vec<SupportedValuesFingerPrint>,
+    /// One entry per INTL_CLASSES the browser has.
+    pub supported_locales: Vec<SupportedLocalesFingerPrint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SupportedValuesFingerPrint {
+    pub key: String,
+    pub values: Vec<String>,
+    pub hash: u64,
+}
+
+/// The subset of PROBE_LOCALES an `Intl` class says it supports.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SupportedLocalesFingerPrint {
+    pub class: String,
+    pub locales: Vec<String>,
+}
+
+impl IntlSupportFingerPrint {
+    /// Returns None if there's no `Intl` at all.
+    pub fn new() -> Option<Self> {
+        let intl = Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+        if intl.is_undefined() {
+            return None;
+        }
+        let supported_values = SUPPORTED_VALUES_KEYS
+            .iter()
+            .filter_map(|key| {
+                let values = strings(&call_method(
+                    &intl,
+                    "supportedValuesOf",
+                    &Array::of1(&(*key).into()),
+                )?);
+                let mut hasher = FnvHasher::default();
+                values.hash(&mut hasher);
+                Some(SupportedValuesFingerPrint {
+                    key: key.to_string(),
+                    hash: hasher.finish(),
+                    values,
+                })
+            })
+            .collect::<Vec<SupportedValuesFingerPrint>>();
+
+        let locales = PROBE_LOCALES
+            .iter()
+            .map(|l| JsValue::from_str(l))
+            .collect::<Array>();
+        let supported_locales = INTL_CLASSES
+            .iter()
+            .filter_map(|class| {
+                let constructor = Reflect::get(&intl, &(*class).into()).ok()?;
+                let supported =
+                    call_method(&constructor, "supportedLocalesOf", &Array::of1(&locales))?;
+                Some(SupportedLocalesFingerPrint {
+                    class: class.to_string(),
+                    locales: strings(&supported),
+                })
+            })
+            .collect::<Vec<SupportedLocalesFingerPrint>>();
+
+        Some(Self {
+            supported_values,
+            supported_locales,
+        })
+    }
+}
+
+fn strings(array: &JsValue) -> Vec<String> {
+    Array::from(array)
+        .into_iter()
+        .filter_map(|s| s.as_string())
+        .collect()
+}
@@ -0,0 +1,186 @@
+use std::hash::{Hash, Hasher};
+
+use js_sys::{Error, Number};
+
+use super::*;
+
+/// Call stack depths are bucketed to this, the exact depth moves with whatever is already on the stack.
+pub const CALL_STACK_DEPTH_BUCKET: u32 = 1000;
+
+/// Values whose `toFixed` output old or non-conforming engines round differently.
+pub static TO_FIXED_CASES: [(f64, u8); 6] = [
+    (1.005, 2),
+    (1.45, 1),
+    (8.345, 2),
+    (0.5, 0),
+    (-1.5e-7, 7),
+    (123.456, 20),
+];
+
+/// Array lengths we check sort stability on. V8 before 7.0 used a stable insertion sort up to 10 elements
+/// and an unstable quicksort above.
+pub static SORT_STABILITY_LENGTHS: [u32; 2] = [10, 100];
+
+/// The built-ins whose own property names we record in enumeration order.
+pub static PROTOTYPE_KEY_OBJECTS: [&[&str]; 8] = [
+    &["Object", "prototype"],
+    &["Array", "prototype"],
+    &["String", "prototype"],
+    &["Function", "prototype"],
+    &["Promise", "prototype"],
+    &["Math"],
+    &["JSON"],
+    &["Reflect"],
+];
+
+/// How `Error().stack` lays out its frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum StackFormat {
+    /// "Error: message\n    at f (url:1:2)"
+    V8,
+    /// "f@url:1:2", with wasm frames as "f@url:wasm-function[1]:0x2"
+    SpiderMonkey,
+    /// "f@url:1:2", with wasm frames as "<?>.wasm-function[1]@[wasm code]"
+    JavaScriptCore,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct JsQuirksFingerPrint {
+    pub error_stack_format: StackFormat,
+    /// `eval.toString().length`, 33 on V8 and 37 on SpiderMonkey and JavaScriptCore.
+    pub eval_to_string_length: Option<u32>,
+    pub native_function_to_string_length: Option<u32>,
+    /// None if the page's CSP doesn't allow `new Function`.
+    pub call_stack_depth_bucket: Option<u32>,
+    /// The order `sort` leaves an array in with an inconsistent comparator, which depends on the sort algorithm.
+    pub sort_signature: Option<String>,
+    /// Whether `sort` keeps elements with equal keys in input order, one entry per SORT_STABILITY_LENGTHS.
+    pub sort_stable: Vec<bool>,
+    /// One entry per TO_FIXED_CASES.
+    pub to_fixed: Vec<String>,
+    /// Hash of the own property names of each of PROTOTYPE_KEY_OBJECTS, in the order the engine lists them.
+    pub prototype_key_orders: Vec<(String, u64)>,
+}
+
+impl JsQuirksFingerPrint {
+    pub fn new() -> Self {
+        let global = JsValue::from(js_sys::global());
+        let to_string_length = |path: &[&str]| {
+            get_path(&global, path)?
+                .dyn_into::<Function>()
+                .ok()
+                .map(|f| f.to_string().length())
+        };
+        let to_fixed = TO_FIXED_CASES
+            .iter()
+            .map(|(value, digits)| {
+                Number::from(*value)
+                    .to_fixed(*digits)
+                    .map(String::from)
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<String>>();
+        let prototype_key_orders = PROTOTYPE_KEY_OBJECTS
+            .iter()
+            .filter_map(|path| {
+                let object = get_path(&global, path)?.dyn_into::<Object>().ok()?;
+                let names = Object::get_own_property_names(&object)
+                    .into_iter()
+                    .filter_map(|name| name.as_string())
+                    .collect::<Vec<String>>();
+                let mut hasher = FnvHasher::default();
+                names.hash(&mut hasher);
+                Some((path.join("."), hasher.finish()))
+            })
+            .collect::<Vec<(String, u64)>>();
+
+        Self {
+            error_stack_format: error_stack_format(),
+            eval_to_string_length: to_string_length(&["eval"]),
+            native_function_to_string_length: to_string_length(&["Math", "max"]),
+            call_stack_depth_bucket: call_stack_depth()
+                .map(|depth| depth / CALL_STACK_DEPTH_BUCKET * CALL_STACK_DEPTH_BUCKET),
+            sort_signature: sort_signature(),
+            sort_stable: SORT_STABILITY_LENGTHS
+                .iter()
+                .map(|length| sort_stable(*length).unwrap_or_default())
+                .collect(),
+            to_fixed,
+            prototype_key_orders,
+        }
+    }
+}
+
+fn error_stack_format() -> StackFormat {
+    let error = Error::new("quirks");
+    let Some(stack) = Reflect::get(&error, &"stack".into())
+        .ok()
+        .and_then(|s| s.as_string())
+    else {
+        return StackFormat::Unknown;
+    };
+    if stack.starts_with("Error: quirks") && stack.contains("    at ") {
+        StackFormat::V8
+    } else if stack.contains("[wasm code]") || stack.contains("[native code]") {
+        StackFormat::JavaScriptCore
+    } else if stack.contains('@') {
+        StackFormat::SpiderMonkey
+    } else {
+        StackFormat::Unknown
+    }
+}
+
+/// Counts how deep a JS function can recurse. Recursing in rust would measure the wasm stack instead.
+fn call_stack_depth() -> Option<u32> {
+    let constructor = Reflect::get(&js_sys::global(), &"Function".into())
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    // throws if the CSP doesn't allow unsafe-eval.
+    let function = Reflect::construct(
+        &constructor,
+        &Array::of1(
+            &"var depth = 0; function f() { depth++; f(); } try { f(); } catch (e) {} return depth;"
+                .into(),
+        ),
+    )
+    .ok()?
+    .dyn_into::<Function>()
+    .ok()?;
+    function
+        .call0(&JsValue::UNDEFINED)
+        .ok()?
+        .as_f64()
+        .map(|d| d as u32)
+}
+
+fn sort_signature() -> Option<String> {
+    let array = (0..12).map(|i| JsValue::from(i as f64)).collect::<Array>();
+    let comparator =
+        Closure::<dyn Fn(f64, f64) -> f64>::new(|a: f64, b: f64| ((a * 7.0 + b * 3.0) % 5.0) - 2.0);
+    call_method(&array, "sort", &Array::of1(comparator.as_ref()))?;
+    Some(String::from(array.join(",")))
+}
+
+/// Sorts records by a key with only a few distinct values, using a valid comparator, and checks records
+/// with the same key kept their input order. A record is `key * length + index`.
+fn sort_stable(length: u32) -> Option<bool> {
+    let key = move |record: f64| (record / length as f64).floor();
+    let array = (0..length)
+        .map(|i| JsValue::from(((i % 3) * length + i) as f64))
+        .collect::<Array>();
+    let comparator = Closure::<dyn Fn(f64, f64) -> f64>::new(move |a: f64, b: f64| key(a) - key(b));
+    call_method(&array, "sort", &Array::of1(comparator.as_ref()))?;
+    let sorted = array
+        .iter()
+        .filter_map(|record| record.as_f64())
+        .collect::<Vec<f64>>();
+    Some(
+        sorted.len() == length as usize
+            && sorted
+                .windows(2)
+                .all(|pair| key(pair[0]) != key(pair[1]) || pair[0] < pair[1]),
+    )
+}
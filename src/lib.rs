@@ -10,32 +10,109 @@ use web_sys::{
 };
 pub mod webgl;
 pub use webgl::*;
+pub mod webgl_extensions;
+pub use webgl_extensions::*;
+pub mod webgl_render;
+pub use webgl_render::*;
+pub mod gpu;
+pub use gpu::*;
 pub mod audio_fingerprint;
 pub use audio_fingerprint::*;
 pub mod canvas;
 pub use canvas::*;
 pub mod fonts;
 pub use fonts::*;
+pub mod eme;
+pub use eme::*;
+pub mod speech;
+pub use speech::*;
+pub mod webgpu;
+pub use webgpu::*;
+pub mod media_queries;
+pub use media_queries::*;
+pub mod window_geometry;
+pub use window_geometry::*;
+pub mod multi_screen;
+pub use multi_screen::*;
+pub mod client_hints;
+pub use client_hints::*;
+pub mod user_agent;
+pub use user_agent::*;
+pub mod engine_detection;
+pub use engine_detection::*;
+pub mod intl;
+pub use intl::*;
+pub mod math;
+pub use math::*;
+pub mod js_quirks;
+pub use js_quirks::*;
+pub mod property_names;
+pub use property_names::*;
+pub mod web_apis;
+pub use web_apis::*;
+pub mod storage;
+pub use storage::*;
+pub mod bot;
+pub use bot::*;
+pub mod tamper;
+pub use tamper::*;
+pub mod consistency;
+pub use consistency::*;
 lazy_static::lazy_static! {
     static ref USER_AGENT:String=window().map(|window|window.navigator().user_agent().unwrap_or_default()).unwrap_or_default().to_ascii_lowercase();
 }
 
-#[derive(Debug, Clone)]
+/// For components whose whole API can be missing, so "this browser doesn't have it" isn't confused with a failed collection.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Support<T> {
+    Supported(T),
+    #[default]
+    Unsupported,
+}
+impl<T> Support<T> {
+    pub fn supported(&self) -> Option<&T> {
+        match self {
+            Support::Supported(t) => Some(t),
+            Support::Unsupported => None,
+        }
+    }
+}
+impl<T> From<Option<T>> for Support<T> {
+    fn from(option: Option<T>) -> Self {
+        option.map_or(Support::Unsupported, Support::Supported)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct FingerPrint {
     pub window_finger_print: Option<WindowFingerPrint>,
     pub audio_finger_print: Option<AudioFingerPrint>,
     pub canvas_finger_print: Option<CanvasFingerPrint>,
     pub webgl_finger_print: Option<WebGLFingerPrint>,
+    pub eme_finger_print: Option<EmeFingerPrint>,
+    pub webgpu_finger_print: Support<WebGpuFingerPrint>,
+    pub math_finger_print: MathFingerPrint,
+    pub js_quirks_finger_print: JsQuirksFingerPrint,
+    pub bot_signals: Option<BotSignals>,
+    pub tamper_finger_print: TamperFingerPrint,
 }
 impl FingerPrint {
     /// Returns None, if we can't get a web_sys::Window.
     pub async fn new() -> Option<Self> {
         let window = window()?;
+        let webgl_finger_print = WebGLFingerPrint::new(&window);
+        let bot_signals = BotSignals::new(&window, webgl_finger_print.as_ref()).await;
         Some(Self {
             window_finger_print: WindowFingerPrint::new(&window).await,
             audio_finger_print: AudioFingerPrint::new(&window).await,
             canvas_finger_print: CanvasFingerPrint::new(&window),
-            webgl_finger_print: WebGLFingerPrint::new(&window),
+            webgl_finger_print,
+            eme_finger_print: EmeFingerPrint::new(&window.navigator()).await,
+            webgpu_finger_print: WebGpuFingerPrint::new(&window.navigator()).await,
+            math_finger_print: MathFingerPrint::new(),
+            js_quirks_finger_print: JsQuirksFingerPrint::new(),
+            bot_signals,
+            tamper_finger_print: TamperFingerPrint::new(),
         })
     }
 }
@@ -44,14 +121,36 @@ impl FingerPrint {
 pub struct WindowFingerPrint {
     pub device_pixel_ratio: f64,
     pub screen_finger_print: ScreenFingerPrint,
+    pub media_query_finger_print: Option<MediaQueryFingerPrint>,
+    pub geometry_finger_print: Option<WindowGeometryFingerPrint>,
+    pub feature_detection_finger_print: FeatureDetectionFingerPrint,
+    pub intl_finger_print: Option<IntlFingerPrint>,
+    pub intl_support_finger_print: Option<IntlSupportFingerPrint>,
+    pub property_names_finger_print: Option<PropertyNamesFingerPrint>,
+    pub web_api_finger_print: WebApiFingerPrint,
+    /// The entries of FONT_LIST that are installed.
+    pub fonts: Option<Vec<String>>,
+    pub storage_finger_print: StorageFingerPrint,
     pub navigator_finger_print: NavigatorFingerPrint,
+    pub speech_finger_print: Option<SpeechFingerPrint>,
     pub indexdb_is_some: bool,
     pub local_storage_is_some: bool,
 }
 impl WindowFingerPrint {
     pub async fn new(window: &Window) -> Option<Self> {
-        let screen_finger_print = ScreenFingerPrint::new(&window.screen().ok()?)?;
+        let mut screen_finger_print = ScreenFingerPrint::new(&window.screen().ok()?)?;
+        screen_finger_print.screens = ScreenDetailFingerPrint::all(window).await;
+        let media_query_finger_print = MediaQueryFingerPrint::new(window);
+        let geometry_finger_print = WindowGeometryFingerPrint::new(window, &screen_finger_print);
+        let feature_detection_finger_print = FeatureDetectionFingerPrint::new();
+        let intl_finger_print = IntlFingerPrint::new();
+        let intl_support_finger_print = IntlSupportFingerPrint::new();
+        let property_names_finger_print = PropertyNamesFingerPrint::new(window);
+        let web_api_finger_print = WebApiFingerPrint::new(window).await;
+        let storage_finger_print = StorageFingerPrint::new(window).await;
+        let fonts = window.document().and_then(|d| detect_font_names(&d));
         let navigator_finger_print = NavigatorFingerPrint::new(window.navigator()).await?;
+        let speech_finger_print = SpeechFingerPrint::new(window).await;
         let device_pixel_ratio = window.device_pixel_ratio();
         let indexdb_is_some = window.indexed_db().ok().flatten().is_some();
         let local_storage_is_some = window.local_storage().ok().flatten().is_some();
@@ -59,7 +158,17 @@ impl WindowFingerPrint {
         Some(Self {
             device_pixel_ratio,
             screen_finger_print,
+            media_query_finger_print,
+            geometry_finger_print,
+            feature_detection_finger_print,
+            intl_finger_print,
+            intl_support_finger_print,
+            property_names_finger_print,
+            web_api_finger_print,
+            fonts,
+            storage_finger_print,
             navigator_finger_print,
+            speech_finger_print,
             indexdb_is_some,
             local_storage_is_some,
         })
@@ -68,15 +177,19 @@ impl WindowFingerPrint {
 
 #[derive(Debug, Clone, Default)]
 pub struct ScreenFingerPrint {
-    height: i32,
-    width: i32,
-    color_depth: i32,
-    pixel_depth: i32,
-    color_gamut: u32,
-    avail_height: i32,
-    avail_width: i32,
-    avail_top: i32,
-    avail_left: i32,
+    pub height: i32,
+    pub width: i32,
+    pub color_depth: i32,
+    pub pixel_depth: i32,
+    pub color_gamut: u32,
+    pub avail_height: i32,
+    pub avail_width: i32,
+    pub avail_top: i32,
+    pub avail_left: i32,
+    /// Whether the desktop spans more than one screen.
+    pub is_extended: Option<bool>,
+    /// Every screen of the desktop, None unless the `window-management` permission was already granted.
+    pub screens: Option<Vec<ScreenDetailFingerPrint>>,
 }
 impl ScreenFingerPrint {
     pub fn new(screen: &Screen) -> Option<Self> {
@@ -89,6 +202,7 @@ impl ScreenFingerPrint {
         let avail_width = screen.avail_width().unwrap_or_default();
         let avail_top = screen.avail_top().unwrap_or_default();
         let avail_left = screen.avail_left().unwrap_or_default();
+        let is_extended = screen_is_extended(screen);
         Some(Self {
             height,
             width,
@@ -99,12 +213,15 @@ impl ScreenFingerPrint {
             avail_width,
             avail_top,
             avail_left,
+            is_extended,
+            screens: None,
         })
     }
 }
 #[derive(Debug, Clone, Default)]
 pub struct NavigatorFingerPrint {
     pub network_information: Option<NetworkInformationFingerPrint>,
+    /// Empty if the user hasn't set it, chrome reports that as null.
     pub do_not_track: String,
     pub geolocation_is_ok: bool,
     pub gamepad_ids: Vec<String>,
@@ -117,8 +234,36 @@ pub struct NavigatorFingerPrint {
     pub video_input: i32,
     pub platform: String,
     pub user_agent: String,
-    pub navigator_property_count: usize,
+    pub client_hints: Support<ClientHintsFingerPrint>,
     pub permission_fingerprint: Option<PermissionFingerPrint>,
+    pub device_memory: Option<f64>,
+    pub cookie_enabled: Option<bool>,
+    pub pdf_viewer_enabled: Option<bool>,
+    pub vendor: Option<String>,
+    pub vendor_sub: Option<String>,
+    pub product_sub: Option<String>,
+    pub app_version: String,
+    /// Firefox only.
+    pub oscpu: Option<String>,
+    /// Firefox only, and frozen to 20181001000000 since Firefox 64.
+    pub build_id: Option<String>,
+    pub webdriver: Option<bool>,
+    pub plugins: Vec<PluginFingerPrint>,
+    pub mime_types: Vec<MimeTypeFingerPrint>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PluginFingerPrint {
+    pub name: String,
+    pub filename: String,
+    pub description: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MimeTypeFingerPrint {
+    pub type_: String,
+    pub description: String,
+    pub suffixes: String,
 }
 
 impl NavigatorFingerPrint {
@@ -126,7 +271,10 @@ impl NavigatorFingerPrint {
         let user_agent = navigator.user_agent().unwrap_or_default();
         let network_information =
             NetworkInformationFingerPrint::from_result(navigator.connection());
-        let do_not_track = String::default(); //navigator.do_not_track(); the fact that this returns a String apparently is panic worthy in chrome.
+        // navigator.do_not_track() panics in chrome, which returns null instead of a string.
+        let property =
+            |key: &str| Reflect::get(&navigator, &key.into()).unwrap_or(JsValue::UNDEFINED);
+        let do_not_track = property("doNotTrack").as_string().unwrap_or_default();
         let geolocation_is_ok = navigator.geolocation().is_ok();
         let gamepad_ids = navigator
             .get_gamepads()
@@ -154,8 +302,8 @@ impl NavigatorFingerPrint {
         let mut audio_input = 0;
         let mut audio_output = 0;
         let mut video_input = 0;
-        if let Some(devices) = navigator.media_devices().ok() {
-            if let Some(promise) = devices.enumerate_devices().ok() {
+        if let Ok(devices) = navigator.media_devices() {
+            if let Ok(promise) = devices.enumerate_devices() {
                 if let Ok(Ok(array)) = wasm_bindgen_futures::JsFuture::from(promise)
                     .await
                     .map(|result| result.dyn_into::<Array>())
@@ -180,10 +328,34 @@ impl NavigatorFingerPrint {
             }
         }
         let platform = navigator.platform().unwrap_or_default();
-        let navigator_property_count = Object::keys(&Object::get_prototype_of(&navigator.as_ref()))
-            .into_iter()
-            .len();
+        let client_hints = ClientHintsFingerPrint::new(&navigator).await;
         let permission_fingerprint = PermissionFingerPrint::new(&navigator).await;
+        let plugins = navigator
+            .plugins()
+            .map(|plugins| {
+                (0..plugins.length())
+                    .filter_map(|i| plugins.item(i))
+                    .map(|p| PluginFingerPrint {
+                        name: p.name(),
+                        filename: p.filename(),
+                        description: p.description(),
+                    })
+                    .collect::<Vec<PluginFingerPrint>>()
+            })
+            .unwrap_or_default();
+        let mime_types = navigator
+            .mime_types()
+            .map(|mime_types| {
+                (0..mime_types.length())
+                    .filter_map(|i| mime_types.item(i))
+                    .map(|m| MimeTypeFingerPrint {
+                        type_: m.type_(),
+                        description: m.description(),
+                        suffixes: m.suffixes(),
+                    })
+                    .collect::<Vec<MimeTypeFingerPrint>>()
+            })
+            .unwrap_or_default();
         Some(Self {
             network_information,
             do_not_track,
@@ -198,31 +370,68 @@ impl NavigatorFingerPrint {
             video_input,
             platform,
             user_agent,
-            navigator_property_count,
+            client_hints,
             permission_fingerprint,
+            device_memory: property("deviceMemory").as_f64(),
+            cookie_enabled: property("cookieEnabled").as_bool(),
+            pdf_viewer_enabled: property("pdfViewerEnabled").as_bool(),
+            vendor: property("vendor").as_string(),
+            vendor_sub: property("vendorSub").as_string(),
+            product_sub: property("productSub").as_string(),
+            app_version: navigator.app_version().unwrap_or_default(),
+            oscpu: property("oscpu").as_string(),
+            build_id: property("buildID").as_string(),
+            webdriver: property("webdriver").as_bool(),
+            plugins,
+            mime_types,
         })
     }
 }
 #[derive(Debug, Clone, Default)]
 pub struct PermissionFingerPrint {
-    accelerometer: Option<u32>,
-    accessibility: Option<u32>,
-    ambient_light_sensor: Option<u32>,
-    camera: Option<u32>,
-    clipboard_read: Option<u32>,
-    clipboard_write: Option<u32>,
-    geolocation: Option<u32>,
-    background_sync: Option<u32>,
-    magnetometer: Option<u32>,
-    microphone: Option<u32>,
-    midi: Option<u32>,
-    notifications: Option<u32>,
-    payment_handler: Option<u32>,
-    persistent_storage: Option<u32>,
-    push: Option<u32>,
+    pub accelerometer: Option<u32>,
+    pub accessibility: Option<u32>,
+    pub ambient_light_sensor: Option<u32>,
+    pub camera: Option<u32>,
+    pub clipboard_read: Option<u32>,
+    pub clipboard_write: Option<u32>,
+    pub geolocation: Option<u32>,
+    pub background_sync: Option<u32>,
+    pub magnetometer: Option<u32>,
+    pub microphone: Option<u32>,
+    pub midi: Option<u32>,
+    pub notifications: Option<u32>,
+    pub payment_handler: Option<u32>,
+    pub persistent_storage: Option<u32>,
+    pub push: Option<u32>,
 }
 
 impl PermissionFingerPrint {
+    /// The permission names `navigator.permissions.query` accepted, whatever their state.
+    pub fn accepted(&self) -> Vec<&'static str> {
+        [
+            ("accelerometer", self.accelerometer),
+            ("accessibility", self.accessibility),
+            ("ambient-light-sensor", self.ambient_light_sensor),
+            ("camera", self.camera),
+            ("clipboard-read", self.clipboard_read),
+            ("clipboard-write", self.clipboard_write),
+            ("geolocation", self.geolocation),
+            ("background-sync", self.background_sync),
+            ("magnetometer", self.magnetometer),
+            ("microphone", self.microphone),
+            ("midi", self.midi),
+            ("notifications", self.notifications),
+            ("payment-handler", self.payment_handler),
+            ("persistent-storage", self.persistent_storage),
+            ("push", self.push),
+        ]
+        .into_iter()
+        .filter(|(_, state)| state.is_some())
+        .map(|(name, _)| name)
+        .collect()
+    }
+
     pub async fn new(navigator: &Navigator) -> Option<Self> {
         let permissions = &navigator.permissions().ok()?;
         let accelerometer = query_permission(permissions, "accelerometer")
@@ -291,6 +500,63 @@ impl PermissionFingerPrint {
     }
 }
 
+/// Calls `target[name](...args)`. For APIs web_sys doesn't bind, or only binds behind `web_sys_unstable_apis`.
+fn call_method(target: &JsValue, name: &str, args: &Array) -> Option<JsValue> {
+    let function = Reflect::get(target, &name.into())
+        .ok()?
+        .dyn_into::<Function>()
+        .ok()?;
+    Reflect::apply(&function, target, args).ok()
+}
+
+/// Calls a method that returns a promise and awaits it, None if the method is missing or the promise rejects.
+async fn promise_result(target: &JsValue, method: &str, args: &Array) -> Option<JsValue> {
+    let promise = call_method(target, method, args)?
+        .dyn_into::<js_sys::Promise>()
+        .ok()?;
+    JsFuture::from(promise).await.ok()
+}
+
+/// Follows `path` from `target`, None if any step is missing.
+fn get_path(target: &JsValue, path: &[&str]) -> Option<JsValue> {
+    let mut value = target.clone();
+    for key in path {
+        value = Reflect::get(&value, &(*key).into()).ok()?;
+        if value.is_undefined() || value.is_null() {
+            return None;
+        }
+    }
+    Some(value)
+}
+
+/// 64 bit FNV-1a. Unlike DefaultHasher the algorithm is fixed, so stored hashes survive a toolchain upgrade.
+/// Lengths are fed as u64, so a hash computed on wasm32 matches one recomputed on a 64 bit server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FnvHasher(u64);
+
+impl Default for FnvHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl std::hash::Hasher for FnvHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write(&(i as u64).to_le_bytes());
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
 async fn query_permission(
     permissions_api: &Permissions,
     permission: &str,
@@ -311,40 +577,34 @@ async fn query_permission(
 
 #[derive(Debug, Clone, Default)]
 pub struct NetworkInformationFingerPrint {
-    downlink: Option<f64>,
-    downlink_max: Option<f64>,
-    effective_type: Option<String>,
-    rtt: Option<f64>,
-    save_data: Option<bool>,
-    type_: Option<String>,
+    pub downlink: Option<f64>,
+    pub downlink_max: Option<f64>,
+    pub effective_type: Option<String>,
+    pub rtt: Option<f64>,
+    pub save_data: Option<bool>,
+    pub type_: Option<String>,
 }
 impl NetworkInformationFingerPrint {
     pub fn from_result(n: Result<NetworkInformation, JsValue>) -> Option<Self> {
         let network = n.ok()?;
         let downlink = js_sys::Reflect::get(&network, &"downlink".into())
             .ok()
-            .map(|r| r.as_f64())
-            .flatten();
+            .and_then(|r| r.as_f64());
         let downlink_max = js_sys::Reflect::get(&network, &"downlinkMax".into())
             .ok()
-            .map(|r| r.as_f64())
-            .flatten();
+            .and_then(|r| r.as_f64());
         let effective_type = js_sys::Reflect::get(&network, &"effectiveType".into())
             .ok()
-            .map(|r| r.as_string())
-            .flatten();
+            .and_then(|r| r.as_string());
         let rtt = js_sys::Reflect::get(&network, &"rtt".into())
             .ok()
-            .map(|r| r.as_f64())
-            .flatten();
+            .and_then(|r| r.as_f64());
         let save_data = js_sys::Reflect::get(&network, &"saveData".into())
             .ok()
-            .map(|r| r.as_bool())
-            .flatten();
+            .and_then(|r| r.as_bool());
         let type_ = js_sys::Reflect::get(&network, &"type".into())
             .ok()
-            .map(|r| r.as_string())
-            .flatten();
+            .and_then(|r| r.as_string());
 
         Some(Self {
             downlink,
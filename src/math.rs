@@ -0,0 +1,63 @@
+use std::f64::consts::PI;
+use std::hash::{Hash, Hasher};
+
+use js_sys::Math;
+
+use super::*;
+
+/// A name for the fingerprint and the call it stands for.
+pub type MathProbe = (&'static str, fn() -> f64);
+
+/// Inputs where libm implementations are known to round differently.
+pub static MATH_BATTERY: [MathProbe; 24] = [
+    ("acos(0.123124234234234242)", || {
+        Math::acos(0.123_124_234_234_234_24)
+    }),
+    ("acosh(1e308)", || Math::acosh(1e308)),
+    ("asin(0.123124234234234242)", || {
+        Math::asin(0.123_124_234_234_234_24)
+    }),
+    ("asinh(1)", || Math::asinh(1.0)),
+    ("atan(2)", || Math::atan(2.0)),
+    ("atanh(0.5)", || Math::atanh(0.5)),
+    ("atan2(0.5, 1e-10)", || Math::atan2(0.5, 1e-10)),
+    ("cbrt(100)", || Math::cbrt(100.0)),
+    ("cos(10.000000000123)", || Math::cos(10.000000000123)),
+    ("cosh(1)", || Math::cosh(1.0)),
+    ("exp(1)", || Math::exp(1.0)),
+    ("expm1(1)", || Math::expm1(1.0)),
+    ("log(10)", || Math::log(10.0)),
+    ("log1p(10)", || Math::log1p(10.0)),
+    ("log2(7)", || Math::log2(7.0)),
+    ("log10(7)", || Math::log10(7.0)),
+    ("sin(-1e300)", || Math::sin(-1e300)),
+    ("sinh(1)", || Math::sinh(1.0)),
+    ("tan(-1e300)", || Math::tan(-1e300)),
+    ("tanh(1)", || Math::tanh(1.0)),
+    ("pow(PI, -100)", || Math::pow(PI, -100.0)),
+    ("pow(2, -1074)", || Math::pow(2.0, -1074.0)),
+    ("pow(1.0000001, 1e9)", || Math::pow(1.0000001, 1e9)),
+    ("hypot(1e-200, 1e-200)", || Math::hypot(1e-200, 1e-200)),
+];
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MathFingerPrint {
+    /// The exact bit pattern of every MATH_BATTERY result, in the same order.
+    pub results: Vec<(&'static str, u64)>,
+    pub hash: u64,
+}
+
+impl MathFingerPrint {
+    pub fn new() -> Self {
+        let results = MATH_BATTERY
+            .iter()
+            .map(|(name, f)| (*name, f().to_bits()))
+            .collect::<Vec<(&'static str, u64)>>();
+        let mut hasher = FnvHasher::default();
+        results.hash(&mut hasher);
+        Self {
+            hash: hasher.finish(),
+            results,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use web_sys::ScreenColorGamut;
+
+use super::*;
+
+/// Which value of each media feature matches. None means no value matched, usually because the browser doesn't know the feature.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQueryFingerPrint {
+    pub prefers_color_scheme: Option<String>,
+    pub prefers_reduced_motion: Option<String>,
+    pub prefers_contrast: Option<String>,
+    pub prefers_reduced_transparency: Option<String>,
+    pub forced_colors: Option<String>,
+    pub inverted_colors: Option<String>,
+    pub dynamic_range: Option<String>,
+    pub video_dynamic_range: Option<String>,
+    pub pointer: Option<String>,
+    pub any_pointer: Option<String>,
+    pub hover: Option<String>,
+    pub any_hover: Option<String>,
+    pub display_mode: Option<String>,
+    pub monochrome: Option<bool>,
+    pub update: Option<String>,
+    pub color_gamut: Option<String>,
+}
+
+impl MediaQueryFingerPrint {
+    pub fn new(window: &Window) -> Option<Self> {
+        let feature = |name, values| media_feature(window, name, values);
+        Some(Self {
+            prefers_color_scheme: feature("prefers-color-scheme", &["light", "dark"]),
+            prefers_reduced_motion: feature("prefers-reduced-motion", &["no-preference", "reduce"]),
+            prefers_contrast: feature(
+                "prefers-contrast",
+                &["no-preference", "more", "less", "custom"],
+            ),
+            prefers_reduced_transparency: feature(
+                "prefers-reduced-transparency",
+                &["no-preference", "reduce"],
+            ),
+            forced_colors: feature("forced-colors", &["none", "active"]),
+            inverted_colors: feature("inverted-colors", &["none", "inverted"]),
+            // standard matches every screen, so it has to be tried after high.
+            dynamic_range: feature("dynamic-range", &["high", "standard"]),
+            video_dynamic_range: feature("video-dynamic-range", &["high", "standard"]),
+            pointer: feature("pointer", &["fine", "coarse", "none"]),
+            any_pointer: feature("any-pointer", &["fine", "coarse", "none"]),
+            hover: feature("hover", &["hover", "none"]),
+            any_hover: feature("any-hover", &["hover", "none"]),
+            display_mode: feature(
+                "display-mode",
+                &[
+                    "fullscreen",
+                    "standalone",
+                    "minimal-ui",
+                    "browser",
+                    "window-controls-overlay",
+                    "picture-in-picture",
+                ],
+            ),
+            monochrome: matches(window, "(monochrome)"),
+            update: feature("update", &["fast", "slow", "none"]),
+            // each gamut matches every narrower one too, so go from widest to narrowest.
+            color_gamut: feature("color-gamut", &["rec2020", "p3", "srgb"]),
+        })
+    }
+
+    /// Whether the media query color gamut agrees with `Screen.colorGamut`.
+    /// None if either side is missing, which is the case in most browsers as `Screen.colorGamut` is rarely implemented.
+    pub fn color_gamut_matches_screen(&self, screen: &ScreenFingerPrint) -> Option<bool> {
+        let screen_gamut = match screen.color_gamut {
+            g if g == ScreenColorGamut::Srgb as u32 => "srgb",
+            g if g == ScreenColorGamut::P3 as u32 => "p3",
+            g if g == ScreenColorGamut::Rec2020 as u32 => "rec2020",
+            _ => return None,
+        };
+        let media_gamut = self.color_gamut.as_deref()?;
+        Some(screen_gamut == media_gamut)
+    }
+}
+
+fn matches(window: &Window, query: &str) -> Option<bool> {
+    Some(window.match_media(query).ok()??.matches())
+}
+
+fn media_feature(window: &Window, name: &str, values: &[&str]) -> Option<String> {
+    values
+        .iter()
+        .find(|value| matches(window, &format!("({name}: {value})")).unwrap_or_default())
+        .map(|value| value.to_string())
+}
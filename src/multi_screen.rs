@@ -0,0 +1,65 @@
+use js_sys::Promise;
+
+use super::*;
+
+/// One entry of `getScreenDetails().screens`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScreenDetailFingerPrint {
+    pub label: Option<String>,
+    pub width: i32,
+    pub height: i32,
+    pub left: i32,
+    pub top: i32,
+    pub device_pixel_ratio: f64,
+    pub is_primary: bool,
+    pub is_internal: bool,
+}
+
+impl ScreenDetailFingerPrint {
+    /// Only asks for the screen details if the `window-management` permission is already granted,
+    /// `getScreenDetails()` would prompt otherwise.
+    pub async fn all(window: &Window) -> Option<Vec<Self>> {
+        if window_management_permission(window).await? != PermissionState::Granted {
+            return None;
+        }
+        let promise = call_method(window, "getScreenDetails", &Array::new())?
+            .dyn_into::<Promise>()
+            .ok()?;
+        let details = JsFuture::from(promise).await.ok()?;
+        let screens = Reflect::get(&details, &"screens".into())
+            .ok()?
+            .dyn_into::<Array>()
+            .ok()?;
+        Some(screens.iter().map(|screen| Self::new(&screen)).collect())
+    }
+
+    fn new(screen: &JsValue) -> Self {
+        let get = |key: &str| Reflect::get(screen, &key.into()).unwrap_or(JsValue::UNDEFINED);
+        let int = |key: &str| get(key).as_f64().unwrap_or_default() as i32;
+        Self {
+            label: get("label").as_string(),
+            width: int("width"),
+            height: int("height"),
+            left: int("left"),
+            top: int("top"),
+            device_pixel_ratio: get("devicePixelRatio").as_f64().unwrap_or_default(),
+            is_primary: get("isPrimary").as_bool().unwrap_or_default(),
+            is_internal: get("isInternal").as_bool().unwrap_or_default(),
+        }
+    }
+}
+
+/// The state of the `window-management` permission. Chromium before the rename rejects that name,
+/// so we fall back to `window-placement`. None where neither name is known.
+pub async fn window_management_permission(window: &Window) -> Option<PermissionState> {
+    let permissions = window.navigator().permissions().ok()?;
+    match query_permission(&permissions, "window-management").await {
+        Some(state) => Some(state),
+        None => query_permission(&permissions, "window-placement").await,
+    }
+}
+
+/// `screen.isExtended`, None where the Window Management API isn't implemented.
+pub fn screen_is_extended(screen: &Screen) -> Option<bool> {
+    Reflect::get(screen, &"isExtended".into()).ok()?.as_bool()
+}
@@ -0,0 +1,103 @@
+use std::hash::{Hash, Hasher};
+
+use web_sys::HtmlIFrameElement;
+
+use super::*;
+
+/// Property names of `Navigator.prototype` and `window`, each compared against a fresh about:blank iframe.
+/// Globals injected into the page by extensions or automation only show up in the top window, so they land in `added`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PropertyNamesFingerPrint {
+    pub navigator: PropertyNameSet,
+    pub window: PropertyNameSet,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PropertyNameSet {
+    /// Sorted, so the hash doesn't depend on the engine's enumeration order.
+    pub names: Vec<String>,
+    pub hash: u64,
+    /// Names the baseline doesn't have. Both are empty if we couldn't create a baseline.
+    pub added: Vec<String>,
+    /// Names only the baseline has.
+    pub missing: Vec<String>,
+}
+
+impl PropertyNamesFingerPrint {
+    pub fn new(window: &Window) -> Option<Self> {
+        let navigator_prototype = |window: &Window| {
+            Object::get_prototype_of(window.navigator().as_ref())
+                .dyn_into::<Object>()
+                .ok()
+        };
+        let iframe = baseline_iframe(window);
+        let baseline = iframe.as_ref().and_then(|iframe| iframe.content_window());
+        let baseline_names = |object: Option<Object>| object.map(|o| property_names(&o));
+
+        let navigator = PropertyNameSet::new(
+            property_names(&navigator_prototype(window)?),
+            baseline_names(baseline.as_ref().and_then(navigator_prototype)),
+        );
+        let window_names = PropertyNameSet::new(
+            property_names(window.as_ref()),
+            baseline_names(baseline.map(Object::from)),
+        );
+        if let Some(iframe) = iframe {
+            iframe.remove();
+        }
+        Some(Self {
+            navigator,
+            window: window_names,
+        })
+    }
+}
+
+impl PropertyNameSet {
+    pub fn new(mut names: Vec<String>, baseline: Option<Vec<String>>) -> Self {
+        names.sort();
+        names.dedup();
+        let mut hasher = FnvHasher::default();
+        names.hash(&mut hasher);
+        let (added, missing) = match baseline {
+            Some(baseline) => (
+                names
+                    .iter()
+                    .filter(|n| !baseline.contains(n))
+                    .cloned()
+                    .collect(),
+                baseline
+                    .iter()
+                    .filter(|n| !names.contains(n))
+                    .cloned()
+                    .collect(),
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        Self {
+            hash: hasher.finish(),
+            names,
+            added,
+            missing,
+        }
+    }
+}
+
+fn property_names(object: &Object) -> Vec<String> {
+    Object::get_own_property_names(object)
+        .into_iter()
+        .filter_map(|name| name.as_string())
+        .collect()
+}
+
+/// An iframe attached to the body, since only an attached iframe has a content window. The caller removes it.
+fn baseline_iframe(window: &Window) -> Option<HtmlIFrameElement> {
+    let document = window.document()?;
+    let iframe = document
+        .create_element("iframe")
+        .ok()?
+        .dyn_into::<HtmlIFrameElement>()
+        .ok()?;
+    iframe.style().set_property("display", "none").ok()?;
+    document.body()?.append_child(&iframe).ok()?;
+    Some(iframe)
+}
@@ -0,0 +1,74 @@
+use std::hash::{Hash, Hasher};
+
+use js_sys::Promise;
+use web_sys::{SpeechSynthesis, SpeechSynthesisVoice};
+
+use super::*;
+
+/// How long we wait for `voiceschanged` before taking whatever voices are there.
+pub const VOICES_TIMEOUT_MS: i32 = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct SpeechFingerPrint {
+    pub voices: Vec<SpeechVoiceFingerPrint>,
+    /// Hash of the voices sorted by name and lang, so it doesn't depend on the order the browser lists them in.
+    pub voices_hash: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SpeechVoiceFingerPrint {
+    pub name: String,
+    pub lang: String,
+    pub local_service: bool,
+    pub default: bool,
+}
+
+impl SpeechFingerPrint {
+    pub async fn new(window: &Window) -> Option<Self> {
+        let synth = window.speech_synthesis().ok()?;
+        // chrome loads the voice list lazily, so the first call is usually empty.
+        if synth.get_voices().length() == 0 {
+            voices_changed(window, &synth).await;
+        }
+        let voices = synth
+            .get_voices()
+            .into_iter()
+            .filter_map(|v| v.dyn_into::<SpeechSynthesisVoice>().ok())
+            .map(|v| SpeechVoiceFingerPrint {
+                name: v.name(),
+                lang: v.lang(),
+                local_service: v.local_service(),
+                default: v.default(),
+            })
+            .collect::<Vec<SpeechVoiceFingerPrint>>();
+
+        let mut sorted = voices.clone();
+        sorted.sort_by(|a, b| (&a.name, &a.lang).cmp(&(&b.name, &b.lang)));
+        let mut hasher = FnvHasher::default();
+        sorted.hash(&mut hasher);
+        let voices_hash = hasher.finish();
+
+        Some(Self {
+            voices,
+            voices_hash,
+        })
+    }
+}
+
+/// Resolves on the first `voiceschanged` event, or after VOICES_TIMEOUT_MS.
+async fn voices_changed(window: &Window, synth: &SpeechSynthesis) -> Option<()> {
+    let mut timeout = None;
+    let promise = Promise::new(&mut |resolve, _reject| {
+        synth.set_onvoiceschanged(Some(&resolve));
+        timeout = window
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, VOICES_TIMEOUT_MS)
+            .ok();
+    });
+    let result = JsFuture::from(promise).await;
+    // whichever of the two fired first, drop the other so nothing stale stays attached.
+    synth.set_onvoiceschanged(None);
+    if let Some(timeout) = timeout {
+        window.clear_timeout_with_handle(timeout);
+    }
+    result.ok().map(|_| ())
+}
@@ -0,0 +1,143 @@
+use js_sys::Promise;
+use web_sys::Storage;
+
+use super::*;
+
+/// The name of the database, cache and file we create to test writes. All of them are deleted again.
+pub const STORAGE_PROBE_NAME: &str = "fingerprint-rs-probe";
+
+/// Chrome used to give incognito windows a fixed quota of about 100MiB. A power of two, so it lines up with the quota buckets.
+pub const LEGACY_INCOGNITO_QUOTA_MB: u64 = 128;
+
+/// The `*_write` fields are None if the API doesn't exist, and Some(false) if it exists but the write failed.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct StorageFingerPrint {
+    /// `navigator.storage.estimate().quota` in MiB, rounded down to a power of two since the raw value follows free disk space.
+    pub quota_mb: Option<u64>,
+    pub persisted: Option<bool>,
+    /// `performance.memory.jsHeapSizeLimit` in MiB, chromium only.
+    pub js_heap_size_limit_mb: Option<u64>,
+    pub local_storage_write: Option<bool>,
+    pub session_storage_write: Option<bool>,
+    pub indexed_db_write: Option<bool>,
+    pub cache_api_write: Option<bool>,
+    pub opfs_write: Option<bool>,
+}
+
+impl StorageFingerPrint {
+    pub async fn new(window: &Window) -> Self {
+        let storage_manager = get_path(window, &["navigator", "storage"]);
+        let (quota_mb, persisted) = match &storage_manager {
+            Some(storage_manager) => (
+                promise_result(storage_manager, "estimate", &Array::new())
+                    .await
+                    .and_then(|estimate| get_path(&estimate, &["quota"])?.as_f64())
+                    .map(|quota| quota_bucket(quota as u64 / (1 << 20))),
+                promise_result(storage_manager, "persisted", &Array::new())
+                    .await
+                    .and_then(|p| p.as_bool()),
+            ),
+            None => (None, None),
+        };
+        let js_heap_size_limit_mb = get_path(window, &["performance", "memory", "jsHeapSizeLimit"])
+            .and_then(|limit| limit.as_f64())
+            .map(|limit| limit as u64 / (1 << 20));
+
+        Self {
+            quota_mb,
+            persisted,
+            js_heap_size_limit_mb,
+            local_storage_write: window
+                .local_storage()
+                .ok()
+                .flatten()
+                .map(|s| storage_write(&s)),
+            session_storage_write: window
+                .session_storage()
+                .ok()
+                .flatten()
+                .map(|s| storage_write(&s)),
+            indexed_db_write: match get_path(window, &["indexedDB"]) {
+                Some(indexed_db) => Some(indexed_db_write(&indexed_db).await.is_some()),
+                None => None,
+            },
+            cache_api_write: match get_path(window, &["caches"]) {
+                Some(caches) => Some(cache_api_write(&caches).await.is_some()),
+                None => None,
+            },
+            opfs_write: match storage_manager
+                .filter(|s| Reflect::has(s, &"getDirectory".into()).unwrap_or_default())
+            {
+                Some(storage_manager) => Some(opfs_write(&storage_manager).await.is_some()),
+                None => None,
+            },
+        }
+    }
+
+    /// Incognito chromium caps the quota below twice the JS heap limit, and both private Safari and private Firefox
+    /// refuse OPFS. A small legacy quota or a failing IndexedDB open is also counted.
+    /// The quota is only known to its bucket, so the heap limit is bucketed the same way before comparing.
+    pub fn likely_private_mode(&self) -> bool {
+        let small_quota = match (self.quota_mb, self.js_heap_size_limit_mb) {
+            (Some(quota), Some(heap_limit)) => quota < quota_bucket(heap_limit * 2),
+            (Some(quota), None) => quota < LEGACY_INCOGNITO_QUOTA_MB,
+            _ => false,
+        };
+        small_quota || self.opfs_write == Some(false) || self.indexed_db_write == Some(false)
+    }
+}
+
+/// Rounds down to a power of two.
+fn quota_bucket(mb: u64) -> u64 {
+    match mb {
+        0 => 0,
+        mb => 1 << (63 - mb.leading_zeros()),
+    }
+}
+
+fn storage_write(storage: &Storage) -> bool {
+    let written = storage.set_item(STORAGE_PROBE_NAME, "1").is_ok()
+        && storage
+            .get_item(STORAGE_PROBE_NAME)
+            .ok()
+            .flatten()
+            .as_deref()
+            == Some("1");
+    _ = storage.remove_item(STORAGE_PROBE_NAME);
+    written
+}
+
+async fn indexed_db_write(indexed_db: &JsValue) -> Option<()> {
+    let request = call_method(indexed_db, "open", &Array::of1(&STORAGE_PROBE_NAME.into()))?;
+    let opened = Promise::new(&mut |resolve, reject| {
+        _ = Reflect::set(&request, &"onsuccess".into(), &resolve);
+        _ = Reflect::set(&request, &"onerror".into(), &reject);
+    });
+    let opened = JsFuture::from(opened).await;
+    if let Some(db) = get_path(&request, &["result"]) {
+        call_method(&db, "close", &Array::new());
+    }
+    call_method(
+        indexed_db,
+        "deleteDatabase",
+        &Array::of1(&STORAGE_PROBE_NAME.into()),
+    );
+    opened.ok().map(|_| ())
+}
+
+async fn cache_api_write(caches: &JsValue) -> Option<()> {
+    let name = JsValue::from(STORAGE_PROBE_NAME);
+    let opened = promise_result(caches, "open", &Array::of1(&name)).await;
+    promise_result(caches, "delete", &Array::of1(&name)).await;
+    opened.map(|_| ())
+}
+
+async fn opfs_write(storage_manager: &JsValue) -> Option<()> {
+    let root = promise_result(storage_manager, "getDirectory", &Array::new()).await?;
+    let options = Object::new();
+    Reflect::set(&options, &"create".into(), &true.into()).ok()?;
+    let name = JsValue::from(STORAGE_PROBE_NAME);
+    let created = promise_result(&root, "getFileHandle", &Array::of2(&name, &options)).await;
+    promise_result(&root, "removeEntry", &Array::of1(&name)).await;
+    created.map(|_| ())
+}
@@ -0,0 +1,230 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyKind {
+    Getter,
+    Method,
+}
+
+/// A native property an anti-detect browser might override, and the fingerprint field it feeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TamperTarget {
+    pub prototype: &'static [&'static str],
+    /// Where the instance lives, to look for an own property shadowing the prototype. None for WebGL, which has no global instance.
+    pub instance: Option<&'static [&'static str]>,
+    pub property: &'static str,
+    pub kind: PropertyKind,
+    pub field: &'static str,
+}
+
+const fn navigator(property: &'static str, field: &'static str) -> TamperTarget {
+    TamperTarget {
+        prototype: &["Navigator", "prototype"],
+        instance: Some(&["navigator"]),
+        property,
+        kind: PropertyKind::Getter,
+        field,
+    }
+}
+
+const fn screen(property: &'static str, field: &'static str) -> TamperTarget {
+    TamperTarget {
+        prototype: &["Screen", "prototype"],
+        instance: Some(&["screen"]),
+        property,
+        kind: PropertyKind::Getter,
+        field,
+    }
+}
+
+pub static TAMPER_TARGETS: [TamperTarget; 26] = [
+    navigator(
+        "hardwareConcurrency",
+        "NavigatorFingerPrint.hardware_concurrency",
+    ),
+    navigator("deviceMemory", "NavigatorFingerPrint.device_memory"),
+    navigator("userAgent", "NavigatorFingerPrint.user_agent"),
+    navigator("appVersion", "NavigatorFingerPrint.app_version"),
+    navigator("platform", "NavigatorFingerPrint.platform"),
+    navigator("vendor", "NavigatorFingerPrint.vendor"),
+    navigator("language", "NavigatorFingerPrint.language"),
+    navigator("languages", "NavigatorFingerPrint.languages"),
+    navigator("maxTouchPoints", "NavigatorFingerPrint.max_touch_points"),
+    navigator("webdriver", "NavigatorFingerPrint.webdriver"),
+    navigator("plugins", "NavigatorFingerPrint.plugins"),
+    navigator("mimeTypes", "NavigatorFingerPrint.mime_types"),
+    navigator("doNotTrack", "NavigatorFingerPrint.do_not_track"),
+    navigator("oscpu", "NavigatorFingerPrint.oscpu"),
+    screen("width", "ScreenFingerPrint.width"),
+    screen("height", "ScreenFingerPrint.height"),
+    screen("availWidth", "ScreenFingerPrint.avail_width"),
+    screen("availHeight", "ScreenFingerPrint.avail_height"),
+    screen("availTop", "ScreenFingerPrint.avail_top"),
+    screen("availLeft", "ScreenFingerPrint.avail_left"),
+    screen("colorDepth", "ScreenFingerPrint.color_depth"),
+    screen("pixelDepth", "ScreenFingerPrint.pixel_depth"),
+    TamperTarget {
+        prototype: &["WebGLRenderingContext", "prototype"],
+        instance: None,
+        property: "getParameter",
+        kind: PropertyKind::Method,
+        field: "WebGLParametersFingerPrint",
+    },
+    TamperTarget {
+        prototype: &["WebGL2RenderingContext", "prototype"],
+        instance: None,
+        property: "getParameter",
+        kind: PropertyKind::Method,
+        field: "WebGLParametersFingerPrint",
+    },
+    TamperTarget {
+        prototype: &["WebGLRenderingContext", "prototype"],
+        instance: None,
+        property: "getShaderPrecisionFormat",
+        kind: PropertyKind::Method,
+        field: "ShaderPrecisionFingerPrint",
+    },
+    TamperTarget {
+        prototype: &["WebGL2RenderingContext", "prototype"],
+        instance: None,
+        property: "getShaderPrecisionFormat",
+        kind: PropertyKind::Method,
+        field: "ShaderPrecisionFingerPrint",
+    },
+];
+
+/// The ways a replaced property gives itself away.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Lie {
+    /// `Function.prototype.toString` shows source code instead of `[native code]`.
+    NotNativeCode,
+    /// Shows `[native code]` but without the property's name, which is what a Proxy around a native function prints.
+    Proxy,
+    /// The instance has its own property shadowing the prototype's.
+    InstanceOverride,
+    /// The function has own properties besides `length` and `name`, i.e. a `prototype`.
+    ExtraOwnProperties,
+    /// Calling it on a plain object didn't throw, native getters and methods throw an illegal invocation TypeError.
+    NoIllegalInvocation,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TamperedProperty {
+    pub interface: String,
+    pub property: &'static str,
+    pub field: &'static str,
+    pub lies: Vec<Lie>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TamperFingerPrint {
+    /// `Function.prototype.toString` was replaced itself, so every toString based check is suspect.
+    pub to_string_tampered: bool,
+    /// Only the targets with at least one lie.
+    pub tampered: Vec<TamperedProperty>,
+}
+
+impl TamperFingerPrint {
+    pub fn new() -> Self {
+        let global = JsValue::from(js_sys::global());
+        let to_string = get_path(&global, &["Function", "prototype", "toString"])
+            .and_then(|f| f.dyn_into::<Function>().ok());
+        let Some(to_string) = to_string else {
+            return Self::default();
+        };
+        let to_string_tampered = !function_lies(&to_string, &to_string, "toString").is_empty();
+        let tampered = TAMPER_TARGETS
+            .iter()
+            .filter_map(|target| {
+                let lies = target.lies(&global, &to_string)?;
+                (!lies.is_empty()).then(|| TamperedProperty {
+                    interface: target.prototype[0].to_string(),
+                    property: target.property,
+                    field: target.field,
+                    lies,
+                })
+            })
+            .collect::<Vec<TamperedProperty>>();
+        Self {
+            to_string_tampered,
+            tampered,
+        }
+    }
+
+    /// The fingerprint fields that can't be trusted. Everything in TAMPER_TARGETS if toString itself was replaced.
+    pub fn untrusted_fields(&self) -> Vec<&'static str> {
+        let mut fields = if self.to_string_tampered {
+            TAMPER_TARGETS.iter().map(|t| t.field).collect()
+        } else {
+            self.tampered.iter().map(|t| t.field).collect::<Vec<&str>>()
+        };
+        fields.sort();
+        fields.dedup();
+        fields
+    }
+}
+
+impl TamperTarget {
+    /// None if this browser doesn't have the property at all.
+    fn lies(&self, global: &JsValue, to_string: &Function) -> Option<Vec<Lie>> {
+        let prototype = get_path(global, self.prototype)?
+            .dyn_into::<Object>()
+            .ok()?;
+        let mut lies = Vec::new();
+        let instance_override = self
+            .instance
+            .and_then(|path| get_path(global, path))
+            .and_then(|instance| instance.dyn_into::<Object>().ok())
+            .is_some_and(|instance| {
+                !Object::get_own_property_descriptor(&instance, &self.property.into())
+                    .is_undefined()
+            });
+        if instance_override {
+            lies.push(Lie::InstanceOverride);
+        }
+        let descriptor = Object::get_own_property_descriptor(&prototype, &self.property.into());
+        if descriptor.is_undefined() {
+            // a property the browser doesn't have, unless someone only defined it on the instance.
+            return instance_override.then_some(lies);
+        }
+        let key = match self.kind {
+            PropertyKind::Getter => "get",
+            PropertyKind::Method => "value",
+        };
+        let Some(function) =
+            get_path(&descriptor, &[key]).and_then(|f| f.dyn_into::<Function>().ok())
+        else {
+            // replaced with a data property.
+            lies.push(Lie::NotNativeCode);
+            return Some(lies);
+        };
+        lies.extend(function_lies(&function, to_string, self.property));
+        Some(lies)
+    }
+}
+
+fn function_lies(function: &Function, to_string: &Function, name: &str) -> Vec<Lie> {
+    let mut lies = Vec::new();
+    let source = to_string
+        .call0(function)
+        .ok()
+        .and_then(|s| s.as_string())
+        .unwrap_or_default();
+    if !source.contains("[native code]") {
+        lies.push(Lie::NotNativeCode);
+    } else if !source.contains(name) {
+        lies.push(Lie::Proxy);
+    }
+    let mut own = Object::get_own_property_names(function)
+        .into_iter()
+        .filter_map(|n| n.as_string())
+        .collect::<Vec<String>>();
+    own.sort();
+    if own != ["length", "name"] {
+        lies.push(Lie::ExtraOwnProperties);
+    }
+    if function.call0(&Object::new()).is_ok() {
+        lies.push(Lie::NoIllegalInvocation);
+    }
+    lies
+}
@@ -0,0 +1,245 @@
+use super::*;
+
+/// Browser family as claimed by the User-Agent string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum BrowserFamily {
+    Chrome,
+    Chromium,
+    Edge,
+    Opera,
+    SamsungInternet,
+    Yandex,
+    Vivaldi,
+    Firefox,
+    Safari,
+    InternetExplorer,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Engine {
+    Blink,
+    WebKit,
+    Gecko,
+    Trident,
+    EdgeHTML,
+    #[default]
+    Unknown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OsFamily {
+    Windows,
+    MacOs,
+    Ios,
+    Android,
+    ChromeOs,
+    Linux,
+    #[default]
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DeviceClass {
+    Desktop,
+    Mobile,
+    Tablet,
+    #[default]
+    Unknown,
+}
+
+/// Generic HTTP clients that announce themselves in the User-Agent.
+pub static HTTP_CLIENTS: [&str; 9] = [
+    "curl/",
+    "wget/",
+    "python-requests/",
+    "python-urllib/",
+    "go-http-client/",
+    "okhttp/",
+    "axios/",
+    "node-fetch/",
+    "java/",
+];
+
+/// A User-Agent string split into what it claims to be. Everything here is only as trustworthy as the string itself.
+///
+/// Versions are kept as the dotted string the UA has, i.e. "120.0.6099.109", and the OS version uses dots
+/// even where the UA uses underscores.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct UserAgent {
+    pub browser: BrowserFamily,
+    pub browser_version: Option<String>,
+    pub engine: Engine,
+    pub engine_version: Option<String>,
+    pub os: OsFamily,
+    pub os_version: Option<String>,
+    pub device: DeviceClass,
+    /// A crawler or an HTTP library rather than a browser.
+    pub bot: bool,
+    /// A browser that says it's running headless, i.e. HeadlessChrome.
+    pub headless: bool,
+    /// An Android WebView, which marks itself with `; wv)`, or `Version/4.0` next to `Chrome/` before Android 5.
+    pub webview: bool,
+}
+
+impl UserAgent {
+    /// Never fails, anything we don't recognise is Other or Unknown.
+    pub fn parse(user_agent: &str) -> Self {
+        let ua = user_agent.trim();
+        let lower = ua.to_ascii_lowercase();
+        let (os, os_version) = os(ua);
+        let (browser, browser_version) = browser(ua);
+        let (engine, engine_version) = engine(ua, os);
+        let device = device(ua, os);
+        let headless = lower.contains("headless") || lower.contains("phantomjs");
+        let webview = os == OsFamily::Android
+            && (ua.contains("; wv)") || (ua.contains("Version/") && ua.contains("Chrome/")));
+        let bot = HTTP_CLIENTS.iter().any(|c| lower.starts_with(c))
+            || lower
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|word| {
+                    // cubot is a phone brand, not a crawler.
+                    (word.ends_with("bot") && word != "cubot")
+                        || word.contains("crawler")
+                        || word.contains("spider")
+                        || word == "slurp"
+                        || word == "facebookexternalhit"
+                });
+        Self {
+            browser,
+            browser_version,
+            engine,
+            engine_version,
+            os,
+            os_version,
+            device,
+            bot,
+            headless,
+            webview,
+        }
+    }
+}
+
+impl NavigatorFingerPrint {
+    pub fn parsed_user_agent(&self) -> UserAgent {
+        UserAgent::parse(&self.user_agent)
+    }
+}
+
+/// The version following `token`, i.e. "120.0.1" for "Chrome/" in "Chrome/120.0.1 Safari/537.36".
+fn version_after(ua: &str, token: &str) -> Option<String> {
+    let start = ua.find(token)? + token.len();
+    let version = ua[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == '_')
+        .collect::<String>()
+        .replace('_', ".");
+    let version = version.trim_end_matches('.');
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+fn browser(ua: &str) -> (BrowserFamily, Option<String>) {
+    // Order matters, every chromium fork also claims to be Chrome and Safari.
+    let tokens: [(&str, BrowserFamily); 16] = [
+        ("Edg/", BrowserFamily::Edge),
+        ("EdgA/", BrowserFamily::Edge),
+        ("EdgiOS/", BrowserFamily::Edge),
+        ("Edge/", BrowserFamily::Edge),
+        ("OPR/", BrowserFamily::Opera),
+        ("OPiOS/", BrowserFamily::Opera),
+        ("SamsungBrowser/", BrowserFamily::SamsungInternet),
+        ("YaBrowser/", BrowserFamily::Yandex),
+        ("Vivaldi/", BrowserFamily::Vivaldi),
+        ("Firefox/", BrowserFamily::Firefox),
+        ("FxiOS/", BrowserFamily::Firefox),
+        ("HeadlessChrome/", BrowserFamily::Chrome),
+        ("CriOS/", BrowserFamily::Chrome),
+        ("Chromium/", BrowserFamily::Chromium),
+        ("Chrome/", BrowserFamily::Chrome),
+        ("MSIE ", BrowserFamily::InternetExplorer),
+    ];
+    if let Some((token, family)) = tokens.iter().find(|(token, _)| ua.contains(token)) {
+        return (*family, version_after(ua, token));
+    }
+    if ua.contains("Trident/") {
+        return (BrowserFamily::InternetExplorer, version_after(ua, "rv:"));
+    }
+    if ua.contains("Safari/") && ua.contains("Version/") {
+        return (BrowserFamily::Safari, version_after(ua, "Version/"));
+    }
+    (BrowserFamily::Other, None)
+}
+
+fn engine(ua: &str, os: OsFamily) -> (Engine, Option<String>) {
+    if ua.contains("Trident/") {
+        (Engine::Trident, version_after(ua, "Trident/"))
+    } else if ua.contains("Edge/") {
+        (Engine::EdgeHTML, version_after(ua, "Edge/"))
+    } else if ua.contains("AppleWebKit/") {
+        // every browser on iOS has to use WebKit, whatever it calls itself.
+        match version_after(ua, "Chrome/") {
+            Some(version) if os != OsFamily::Ios => (Engine::Blink, Some(version)),
+            _ => (Engine::WebKit, version_after(ua, "AppleWebKit/")),
+        }
+    } else if ua.contains("Gecko/") || ua.contains("Firefox/") {
+        (Engine::Gecko, version_after(ua, "rv:"))
+    } else {
+        (Engine::Unknown, None)
+    }
+}
+
+fn os(ua: &str) -> (OsFamily, Option<String>) {
+    if let Some(nt) = version_after(ua, "Windows NT ") {
+        // Windows 11 still sends 10.0.
+        let version = match nt.as_str() {
+            "10.0" => "10",
+            "6.3" => "8.1",
+            "6.2" => "8",
+            "6.1" => "7",
+            "6.0" => "Vista",
+            "5.1" | "5.2" => "XP",
+            other => other,
+        };
+        (OsFamily::Windows, Some(version.to_string()))
+    } else if ua.contains("Windows") {
+        (OsFamily::Windows, None)
+    } else if ua.contains("iPhone") || ua.contains("iPad") || ua.contains("iPod") {
+        (OsFamily::Ios, version_after(ua, " OS "))
+    } else if ua.contains("Android") {
+        (OsFamily::Android, version_after(ua, "Android "))
+    } else if ua.contains("CrOS") {
+        // CrOS x86_64 14541.0.0, the platform version comes after the architecture.
+        let version = ua
+            .split("CrOS ")
+            .nth(1)
+            .and_then(|rest| rest.split([' ', ')']).nth(1))
+            .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+            .map(str::to_string);
+        (OsFamily::ChromeOs, version)
+    } else if ua.contains("Mac OS X") {
+        (OsFamily::MacOs, version_after(ua, "Mac OS X "))
+    } else if ua.contains("Linux") || ua.contains("X11") {
+        (OsFamily::Linux, None)
+    } else {
+        (OsFamily::Other, None)
+    }
+}
+
+fn device(ua: &str, os: OsFamily) -> DeviceClass {
+    if ua.contains("iPad")
+        || ua.contains("Tablet")
+        || (os == OsFamily::Android && !ua.contains("Mobile"))
+    {
+        DeviceClass::Tablet
+    } else if ua.contains("Mobi") || ua.contains("iPhone") || ua.contains("iPod") {
+        DeviceClass::Mobile
+    } else if matches!(
+        os,
+        OsFamily::Windows | OsFamily::MacOs | OsFamily::Linux | OsFamily::ChromeOs
+    ) {
+        DeviceClass::Desktop
+    } else {
+        DeviceClass::Unknown
+    }
+}
@@ -0,0 +1,170 @@
+use super::*;
+
+/// A capability API. `interface` is the global the browser defines when it implements the API at all,
+/// `entry_point` is where the page reaches it from `window`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WebApi {
+    pub name: &'static str,
+    pub interface: &'static str,
+    pub entry_point: &'static [&'static str],
+    pub policy_feature: Option<&'static str>,
+}
+
+pub static WEB_APIS: [WebApi; 11] = [
+    WebApi {
+        name: "WebUSB",
+        interface: "USB",
+        entry_point: &["navigator", "usb"],
+        policy_feature: Some("usb"),
+    },
+    WebApi {
+        name: "WebSerial",
+        interface: "Serial",
+        entry_point: &["navigator", "serial"],
+        policy_feature: Some("serial"),
+    },
+    WebApi {
+        name: "WebHID",
+        interface: "HID",
+        entry_point: &["navigator", "hid"],
+        policy_feature: Some("hid"),
+    },
+    WebApi {
+        name: "WebBluetooth",
+        interface: "Bluetooth",
+        entry_point: &["navigator", "bluetooth"],
+        policy_feature: Some("bluetooth"),
+    },
+    WebApi {
+        name: "WebMIDI",
+        interface: "MIDIAccess",
+        entry_point: &["navigator", "requestMIDIAccess"],
+        policy_feature: Some("midi"),
+    },
+    WebApi {
+        name: "WebNFC",
+        interface: "NDEFReader",
+        entry_point: &["NDEFReader"],
+        policy_feature: None,
+    },
+    WebApi {
+        name: "WebXR",
+        interface: "XRSystem",
+        entry_point: &["navigator", "xr"],
+        policy_feature: Some("xr-spatial-tracking"),
+    },
+    WebApi {
+        name: "Presentation",
+        interface: "Presentation",
+        entry_point: &["navigator", "presentation"],
+        policy_feature: None,
+    },
+    WebApi {
+        name: "IdleDetection",
+        interface: "IdleDetector",
+        entry_point: &["IdleDetector"],
+        policy_feature: Some("idle-detection"),
+    },
+    WebApi {
+        name: "ContactPicker",
+        interface: "ContactsManager",
+        entry_point: &["navigator", "contacts"],
+        policy_feature: None,
+    },
+    WebApi {
+        name: "FileSystemAccess",
+        interface: "showOpenFilePicker",
+        entry_point: &["showOpenFilePicker"],
+        policy_feature: None,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ApiAvailability {
+    /// The interface isn't defined. Most of these interfaces are only defined in a secure context, so an insecure
+    /// context reports Missing even when the browser implements the API, check `secure_context` before reading anything into it.
+    #[default]
+    Missing,
+    /// The interface is defined but the entry point isn't reachable, i.e. a permissions policy disallows it.
+    Blocked,
+    Available,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebApiFingerPrint {
+    pub secure_context: bool,
+    /// One entry per WEB_APIS, by name.
+    pub apis: Vec<(&'static str, ApiAvailability)>,
+    /// `navigator.bluetooth.getAvailability()`, whether there's a bluetooth adapter.
+    pub bluetooth_available: Option<bool>,
+    pub xr_immersive_vr: Option<bool>,
+    pub xr_immersive_ar: Option<bool>,
+}
+
+impl WebApiFingerPrint {
+    /// None of these calls prompt the user.
+    pub async fn new(window: &Window) -> Self {
+        let apis = WEB_APIS
+            .iter()
+            .map(|api| (api.name, api.availability(window)))
+            .collect::<Vec<(&'static str, ApiAvailability)>>();
+        let bluetooth_available = match get_path(window, &["navigator", "bluetooth"]) {
+            Some(bluetooth) => promise_result(&bluetooth, "getAvailability", &Array::new())
+                .await
+                .and_then(|a| a.as_bool()),
+            None => None,
+        };
+        let (xr_immersive_vr, xr_immersive_ar) = match get_path(window, &["navigator", "xr"]) {
+            Some(xr) => (
+                session_supported(&xr, "immersive-vr").await,
+                session_supported(&xr, "immersive-ar").await,
+            ),
+            None => (None, None),
+        };
+        Self {
+            secure_context: window.is_secure_context(),
+            apis,
+            bluetooth_available,
+            xr_immersive_vr,
+            xr_immersive_ar,
+        }
+    }
+
+    pub fn availability(&self, name: &str) -> ApiAvailability {
+        self.apis
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, a)| *a)
+            .unwrap_or_default()
+    }
+}
+
+impl WebApi {
+    pub fn availability(&self, window: &Window) -> ApiAvailability {
+        if get_path(window, &[self.interface]).is_none() {
+            return ApiAvailability::Missing;
+        }
+        let allowed = self
+            .policy_feature
+            .and_then(|feature| allows_feature(window, feature))
+            .unwrap_or(true);
+        if get_path(window, self.entry_point).is_some() && allowed {
+            ApiAvailability::Available
+        } else {
+            ApiAvailability::Blocked
+        }
+    }
+}
+
+/// Asks `document.permissionsPolicy`, or the older `document.featurePolicy`. None if the browser has neither.
+fn allows_feature(window: &Window, feature: &str) -> Option<bool> {
+    let policy = get_path(window, &["document", "permissionsPolicy"])
+        .or_else(|| get_path(window, &["document", "featurePolicy"]))?;
+    call_method(&policy, "allowsFeature", &Array::of1(&feature.into()))?.as_bool()
+}
+
+async fn session_supported(xr: &JsValue, mode: &str) -> Option<bool> {
+    promise_result(xr, "isSessionSupported", &Array::of1(&mode.into()))
+        .await?
+        .as_bool()
+}
@@ -1,27 +1,381 @@
-use std::{
-    cmp::{max, min},
-    hash::{DefaultHasher, Hasher},
-    i32,
-};
+use std::cmp::{max, min};
 
 use js_sys::{Float32Array, Int32Array, Uint32Array};
 use wasm_bindgen::JsCast;
-use wasm_bindgen_test::console_log;
-use web_sys::{HtmlCanvasElement, ImageData, WebGl2RenderingContext, WebglDebugRendererInfo};
+use web_sys::{
+    HtmlCanvasElement, WebGl2RenderingContext, WebGlBuffer, WebGlContextAttributes,
+    WebGlFramebuffer, WebGlProgram, WebGlRenderingContext, WebGlShader, WebGlShaderPrecisionFormat,
+    WebGlTexture, WebGlUniformLocation, WebglDebugRendererInfo,
+};
 
 use super::*;
 
+/// The parts of a WebGL rendering context the collectors below use.
+/// Implemented for both WebGL1 and WebGL2 so every collector works with either context.
+pub trait WebGlContext: JsCast {
+    /// The `getContext` ids to try, in order.
+    const CONTEXT_IDS: &'static [&'static str];
+    fn get_parameter(&self, pname: u32) -> Result<JsValue, JsValue>;
+    fn get_supported_extensions(&self) -> Option<Array>;
+    fn get_extension(&self, name: &str) -> Result<Option<Object>, JsValue>;
+    fn get_context_attributes(&self) -> Option<WebGlContextAttributes>;
+    fn get_shader_precision_format(
+        &self,
+        shader_type: u32,
+        precision_type: u32,
+    ) -> Option<WebGlShaderPrecisionFormat>;
+    fn create_shader(&self, type_: u32) -> Option<WebGlShader>;
+    fn shader_source(&self, shader: &WebGlShader, source: &str);
+    fn compile_shader(&self, shader: &WebGlShader);
+    fn create_program(&self) -> Option<WebGlProgram>;
+    fn delete_shader(&self, shader: Option<&WebGlShader>);
+    fn delete_program(&self, program: Option<&WebGlProgram>);
+    fn attach_shader(&self, program: &WebGlProgram, shader: &WebGlShader);
+    fn link_program(&self, program: &WebGlProgram);
+    fn use_program(&self, program: Option<&WebGlProgram>);
+    fn create_buffer(&self) -> Option<WebGlBuffer>;
+    fn delete_buffer(&self, buffer: Option<&WebGlBuffer>);
+    fn bind_buffer(&self, target: u32, buffer: Option<&WebGlBuffer>);
+    fn buffer_data_with_array_buffer_view(&self, target: u32, data: &Object, usage: u32);
+    fn get_attrib_location(&self, program: &WebGlProgram, name: &str) -> i32;
+    fn enable_vertex_attrib_array(&self, index: u32);
+    fn vertex_attrib_pointer_with_i32(
+        &self,
+        index: u32,
+        size: i32,
+        type_: u32,
+        normalized: bool,
+        stride: i32,
+        offset: i32,
+    );
+    fn viewport(&self, x: i32, y: i32, width: i32, height: i32);
+    fn clear_color(&self, red: f32, green: f32, blue: f32, alpha: f32);
+    fn clear(&self, mask: u32);
+    fn draw_arrays(&self, mode: u32, first: i32, count: i32);
+    fn get_program_parameter(&self, program: &WebGlProgram, pname: u32) -> JsValue;
+    fn get_uniform_location(
+        &self,
+        program: &WebGlProgram,
+        name: &str,
+    ) -> Option<WebGlUniformLocation>;
+    fn uniform4f(&self, location: Option<&WebGlUniformLocation>, x: f32, y: f32, z: f32, w: f32);
+    fn enable(&self, cap: u32);
+    fn disable(&self, cap: u32);
+    fn blend_func(&self, sfactor: u32, dfactor: u32);
+    fn create_texture(&self) -> Option<WebGlTexture>;
+    fn delete_texture(&self, texture: Option<&WebGlTexture>);
+    fn bind_texture(&self, target: u32, texture: Option<&WebGlTexture>);
+    fn tex_parameteri(&self, target: u32, pname: u32, param: i32);
+    #[allow(clippy::too_many_arguments)]
+    fn tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+        &self,
+        target: u32,
+        level: i32,
+        internalformat: i32,
+        width: i32,
+        height: i32,
+        border: i32,
+        format: u32,
+        type_: u32,
+        pixels: Option<&[u8]>,
+    ) -> Result<(), JsValue>;
+    fn generate_mipmap(&self, target: u32);
+    fn create_framebuffer(&self) -> Option<WebGlFramebuffer>;
+    fn delete_framebuffer(&self, framebuffer: Option<&WebGlFramebuffer>);
+    fn bind_framebuffer(&self, target: u32, framebuffer: Option<&WebGlFramebuffer>);
+    fn framebuffer_texture_2d(
+        &self,
+        target: u32,
+        attachment: u32,
+        textarget: u32,
+        texture: Option<&WebGlTexture>,
+        level: i32,
+    );
+    fn check_framebuffer_status(&self, target: u32) -> u32;
+    #[allow(clippy::too_many_arguments)]
+    fn read_pixels_with_opt_u8_array(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        format: u32,
+        type_: u32,
+        pixels: Option<&mut [u8]>,
+    ) -> Result<(), JsValue>;
+}
+
+macro_rules! impl_webgl_context {
+    ($ty:ty, $ids:expr) => {
+        impl WebGlContext for $ty {
+            const CONTEXT_IDS: &'static [&'static str] = $ids;
+            fn get_parameter(&self, pname: u32) -> Result<JsValue, JsValue> {
+                <$ty>::get_parameter(self, pname)
+            }
+            fn get_supported_extensions(&self) -> Option<Array> {
+                <$ty>::get_supported_extensions(self)
+            }
+            fn get_extension(&self, name: &str) -> Result<Option<Object>, JsValue> {
+                <$ty>::get_extension(self, name)
+            }
+            fn get_context_attributes(&self) -> Option<WebGlContextAttributes> {
+                <$ty>::get_context_attributes(self)
+            }
+            fn get_shader_precision_format(
+                &self,
+                shader_type: u32,
+                precision_type: u32,
+            ) -> Option<WebGlShaderPrecisionFormat> {
+                <$ty>::get_shader_precision_format(self, shader_type, precision_type)
+            }
+            fn create_shader(&self, type_: u32) -> Option<WebGlShader> {
+                <$ty>::create_shader(self, type_)
+            }
+            fn shader_source(&self, shader: &WebGlShader, source: &str) {
+                <$ty>::shader_source(self, shader, source)
+            }
+            fn compile_shader(&self, shader: &WebGlShader) {
+                <$ty>::compile_shader(self, shader)
+            }
+            fn create_program(&self) -> Option<WebGlProgram> {
+                <$ty>::create_program(self)
+            }
+            fn delete_shader(&self, shader: Option<&WebGlShader>) {
+                <$ty>::delete_shader(self, shader)
+            }
+            fn delete_program(&self, program: Option<&WebGlProgram>) {
+                <$ty>::delete_program(self, program)
+            }
+            fn attach_shader(&self, program: &WebGlProgram, shader: &WebGlShader) {
+                <$ty>::attach_shader(self, program, shader)
+            }
+            fn link_program(&self, program: &WebGlProgram) {
+                <$ty>::link_program(self, program)
+            }
+            fn use_program(&self, program: Option<&WebGlProgram>) {
+                <$ty>::use_program(self, program)
+            }
+            fn create_buffer(&self) -> Option<WebGlBuffer> {
+                <$ty>::create_buffer(self)
+            }
+            fn delete_buffer(&self, buffer: Option<&WebGlBuffer>) {
+                <$ty>::delete_buffer(self, buffer)
+            }
+            fn bind_buffer(&self, target: u32, buffer: Option<&WebGlBuffer>) {
+                <$ty>::bind_buffer(self, target, buffer)
+            }
+            fn buffer_data_with_array_buffer_view(&self, target: u32, data: &Object, usage: u32) {
+                <$ty>::buffer_data_with_array_buffer_view(self, target, data, usage)
+            }
+            fn get_attrib_location(&self, program: &WebGlProgram, name: &str) -> i32 {
+                <$ty>::get_attrib_location(self, program, name)
+            }
+            fn enable_vertex_attrib_array(&self, index: u32) {
+                <$ty>::enable_vertex_attrib_array(self, index)
+            }
+            fn vertex_attrib_pointer_with_i32(
+                &self,
+                index: u32,
+                size: i32,
+                type_: u32,
+                normalized: bool,
+                stride: i32,
+                offset: i32,
+            ) {
+                <$ty>::vertex_attrib_pointer_with_i32(
+                    self, index, size, type_, normalized, stride, offset,
+                )
+            }
+            fn viewport(&self, x: i32, y: i32, width: i32, height: i32) {
+                <$ty>::viewport(self, x, y, width, height)
+            }
+            fn clear_color(&self, red: f32, green: f32, blue: f32, alpha: f32) {
+                <$ty>::clear_color(self, red, green, blue, alpha)
+            }
+            fn clear(&self, mask: u32) {
+                <$ty>::clear(self, mask)
+            }
+            fn draw_arrays(&self, mode: u32, first: i32, count: i32) {
+                <$ty>::draw_arrays(self, mode, first, count)
+            }
+            fn get_program_parameter(&self, program: &WebGlProgram, pname: u32) -> JsValue {
+                <$ty>::get_program_parameter(self, program, pname)
+            }
+            fn get_uniform_location(
+                &self,
+                program: &WebGlProgram,
+                name: &str,
+            ) -> Option<WebGlUniformLocation> {
+                <$ty>::get_uniform_location(self, program, name)
+            }
+            fn uniform4f(
+                &self,
+                location: Option<&WebGlUniformLocation>,
+                x: f32,
+                y: f32,
+                z: f32,
+                w: f32,
+            ) {
+                <$ty>::uniform4f(self, location, x, y, z, w)
+            }
+            fn enable(&self, cap: u32) {
+                <$ty>::enable(self, cap)
+            }
+            fn disable(&self, cap: u32) {
+                <$ty>::disable(self, cap)
+            }
+            fn blend_func(&self, sfactor: u32, dfactor: u32) {
+                <$ty>::blend_func(self, sfactor, dfactor)
+            }
+            fn create_texture(&self) -> Option<WebGlTexture> {
+                <$ty>::create_texture(self)
+            }
+            fn delete_texture(&self, texture: Option<&WebGlTexture>) {
+                <$ty>::delete_texture(self, texture)
+            }
+            fn bind_texture(&self, target: u32, texture: Option<&WebGlTexture>) {
+                <$ty>::bind_texture(self, target, texture)
+            }
+            fn tex_parameteri(&self, target: u32, pname: u32, param: i32) {
+                <$ty>::tex_parameteri(self, target, pname, param)
+            }
+            fn tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                &self,
+                target: u32,
+                level: i32,
+                internalformat: i32,
+                width: i32,
+                height: i32,
+                border: i32,
+                format: u32,
+                type_: u32,
+                pixels: Option<&[u8]>,
+            ) -> Result<(), JsValue> {
+                <$ty>::tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                    self,
+                    target,
+                    level,
+                    internalformat,
+                    width,
+                    height,
+                    border,
+                    format,
+                    type_,
+                    pixels,
+                )
+            }
+            fn generate_mipmap(&self, target: u32) {
+                <$ty>::generate_mipmap(self, target)
+            }
+            fn create_framebuffer(&self) -> Option<WebGlFramebuffer> {
+                <$ty>::create_framebuffer(self)
+            }
+            fn delete_framebuffer(&self, framebuffer: Option<&WebGlFramebuffer>) {
+                <$ty>::delete_framebuffer(self, framebuffer)
+            }
+            fn bind_framebuffer(&self, target: u32, framebuffer: Option<&WebGlFramebuffer>) {
+                <$ty>::bind_framebuffer(self, target, framebuffer)
+            }
+            fn framebuffer_texture_2d(
+                &self,
+                target: u32,
+                attachment: u32,
+                textarget: u32,
+                texture: Option<&WebGlTexture>,
+                level: i32,
+            ) {
+                <$ty>::framebuffer_texture_2d(self, target, attachment, textarget, texture, level)
+            }
+            fn check_framebuffer_status(&self, target: u32) -> u32 {
+                <$ty>::check_framebuffer_status(self, target)
+            }
+            fn read_pixels_with_opt_u8_array(
+                &self,
+                x: i32,
+                y: i32,
+                width: i32,
+                height: i32,
+                format: u32,
+                type_: u32,
+                pixels: Option<&mut [u8]>,
+            ) -> Result<(), JsValue> {
+                <$ty>::read_pixels_with_opt_u8_array(
+                    self, x, y, width, height, format, type_, pixels,
+                )
+            }
+        }
+    };
+}
+impl_webgl_context!(WebGlRenderingContext, &["webgl", "experimental-webgl"]);
+impl_webgl_context!(WebGl2RenderingContext, &["webgl2"]);
+
 #[derive(Debug, Clone, Default)]
 pub struct WebGLFingerPrint {
+    pub webgl: Option<WebGLContextFingerPrint>,
+    pub webgl2: Option<WebGLContextFingerPrint>,
+}
+impl WebGLFingerPrint {
+    /// Returns None if neither a webgl2 nor a webgl context can be created.
+    pub fn new(window: &Window) -> Option<Self> {
+        let webgl2 = WebGLContextFingerPrint::new::<WebGl2RenderingContext>(window);
+        let webgl = WebGLContextFingerPrint::new::<WebGlRenderingContext>(window);
+        if webgl.is_none() && webgl2.is_none() {
+            return None;
+        }
+        Some(Self { webgl, webgl2 })
+    }
+    /// The webgl2 fingerprint if we have one, otherwise the webgl one.
+    pub fn primary(&self) -> Option<&WebGLContextFingerPrint> {
+        self.webgl2.as_ref().or(self.webgl.as_ref())
+    }
+    /// The fields that differ between the webgl and webgl2 contexts. Empty unless we got both.
+    pub fn context_differences(&self) -> Vec<&'static str> {
+        let (Some(webgl), Some(webgl2)) = (&self.webgl, &self.webgl2) else {
+            return Vec::new();
+        };
+        let mut differences = Vec::new();
+        if webgl.renderer != webgl2.renderer {
+            differences.push("renderer");
+        }
+        if webgl.context_attributes != webgl2.context_attributes {
+            differences.push("context_attributes");
+        }
+        if webgl.shader_precision != webgl2.shader_precision {
+            differences.push("shader_precision");
+        }
+        // VERSION and SHADING_LANGUAGE_VERSION always name the context's own WebGL version.
+        let without_versions =
+            |parameters: &WebGLParametersFingerPrint| WebGLParametersFingerPrint {
+                version: String::new(),
+                shading_language_version: String::new(),
+                ..parameters.clone()
+            };
+        if webgl.parameters.as_ref().map(without_versions)
+            != webgl2.parameters.as_ref().map(without_versions)
+        {
+            differences.push("parameters");
+        }
+        if webgl.rendering != webgl2.rendering {
+            differences.push("rendering");
+        }
+        differences
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WebGLContextFingerPrint {
     pub renderer: Option<String>,
     pub context_attributes: Option<WebGlContextAttributesFingerPrint>,
     pub shader_precision: Option<ShaderPrecisionFingerPrint>,
     pub supported_extensions: Vec<String>,
     pub parameters: Option<WebGLParametersFingerPrint>,
-    pub webgl_image_hash: Option<u64>,
+    pub extension_parameters: WebGLExtensionParametersFingerPrint,
+    /// Only collected on a webgl2 context.
+    pub webgl2_parameters: Option<WebGL2ParametersFingerPrint>,
+    pub rendering: Option<WebGLRenderingFingerPrint>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub struct WebGlContextAttributesFingerPrint {
     pub alpha_buffer: Option<bool>,
     pub depth_buffer: Option<bool>,
@@ -33,7 +387,7 @@ pub struct WebGlContextAttributesFingerPrint {
     pub preserve_drawing_buffer: Option<bool>,
 }
 impl WebGlContextAttributesFingerPrint {
-    pub fn new(gl: &WebGl2RenderingContext) -> Option<Self> {
+    pub fn new<G: WebGlContext>(gl: &G) -> Option<Self> {
         let attr = gl.get_context_attributes()?;
 
         Some(Self {
@@ -48,16 +402,19 @@ impl WebGlContextAttributesFingerPrint {
         })
     }
 }
-impl WebGLFingerPrint {
-    pub fn new(window: &Window) -> Option<Self> {
+impl WebGLContextFingerPrint {
+    /// Returns None if the browser won't give us a context of type G.
+    pub fn new<G: WebGlContext>(window: &Window) -> Option<Self> {
         let canvas = window
             .document()?
             .create_element("canvas")
             .ok()?
             .dyn_into::<HtmlCanvasElement>()
             .ok()?;
-        let ctx = canvas.get_context("webgl2").ok()??;
-        let gl = ctx.dyn_into::<WebGl2RenderingContext>().ok()?;
+        let ctx = G::CONTEXT_IDS
+            .iter()
+            .find_map(|id| canvas.get_context(id).ok().flatten())?;
+        let gl = ctx.dyn_into::<G>().ok()?;
         let renderer = renderer(&gl);
         let supported_extensions = gl
             .get_supported_extensions()?
@@ -67,25 +424,26 @@ impl WebGLFingerPrint {
         let parameters = WebGLParametersFingerPrint::new(&gl);
         let context_attributes = WebGlContextAttributesFingerPrint::new(&gl);
         let shader_precision = ShaderPrecisionFingerPrint::new(&gl);
+        let extension_parameters =
+            WebGLExtensionParametersFingerPrint::new(&gl, &supported_extensions);
+        let webgl2_parameters = gl
+            .dyn_ref::<WebGl2RenderingContext>()
+            .and_then(WebGL2ParametersFingerPrint::new);
 
-        let canvas = window
-            .document()?
-            .create_element("canvas")
-            .ok()?
-            .dyn_into::<HtmlCanvasElement>()
-            .ok()?;
-        let webgl_image_hash = webgl_img_hash(&gl, &canvas);
+        let rendering = WebGLRenderingFingerPrint::new(&gl);
         Some(Self {
             renderer,
             context_attributes,
             shader_precision,
             supported_extensions,
             parameters,
-            webgl_image_hash,
+            extension_parameters,
+            webgl2_parameters,
+            rendering,
         })
     }
 }
-fn renderer(gl: &WebGl2RenderingContext) -> Option<String> {
+fn renderer<G: WebGlContext>(gl: &G) -> Option<String> {
     // firefox has deprecated the WEBGL_debug_render_info. But webkit has not, but it needs to be accessed through the debug. Doesn't work on safari.
     let renderer = if !USER_AGENT.contains("applewebkit") {
         gl.get_parameter(web_sys::WebGl2RenderingContext::RENDERER)
@@ -100,126 +458,113 @@ fn renderer(gl: &WebGl2RenderingContext) -> Option<String> {
     };
     Some(renderer)
 }
-/// Return's the img hash
-pub fn webgl_img_hash(gl: &WebGl2RenderingContext, canvas: &HtmlCanvasElement) -> Option<u64> {
-    let vertex_shader_src = r#"
-          attribute vec2 position;
-          void main() {
-              gl_Position = vec4(position, 0.0, 1.0);
-          }
-      "#;
-    let fragment_shader_src = r#"
-      precision mediump float;
-          void main() {
-              gl_FragColor = vec4(0.812, 0.195, 0.553, 0.921); // Set line color
-          }
-    "#;
-    let vertex_shader = gl.create_shader(WebGl2RenderingContext::VERTEX_SHADER)?;
-    let fragment_shader = gl.create_shader(WebGl2RenderingContext::FRAGMENT_SHADER)?;
-
-    gl.shader_source(&vertex_shader, vertex_shader_src);
-    gl.shader_source(&fragment_shader, fragment_shader_src);
-
-    gl.compile_shader(&vertex_shader);
-    gl.compile_shader(&fragment_shader);
-
-    let program = gl.create_program()?;
-
-    gl.attach_shader(&program, &vertex_shader);
-    gl.attach_shader(&program, &fragment_shader);
-
-    gl.link_program(&program);
-
-    gl.use_program(Some(&program));
-
-    // Set up vertices to form lines
-    let num_spokes = 137.;
-    let mut vertices: [f32; 548] = [0.; 548];
-    let angle_increment = (2. * std::f32::consts::PI) / num_spokes;
-
-    for i in 0..(num_spokes as usize) {
-        let f = i as f32;
-        let angle = f * angle_increment;
-        // define two points for each line
-        vertices[i * 4] = 0.; // center x
-        vertices[i * 4 + 1] = 0.; // center y
-        vertices[i * 4 + 2] = angle.cos() * (canvas.width() as f32 / 2.); //endpoint x
-        vertices[i * 4 + 3] = angle.sin() * (canvas.height() as f32 / 2.); //endpoint y
-    }
-
-    let vertex_buffer = gl.create_buffer()?;
-
-    gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vertex_buffer));
-    gl.buffer_data_with_array_buffer_view(
-        WebGl2RenderingContext::ARRAY_BUFFER,
-        Float32Array::from(vertices.as_slice()).as_ref(),
-        WebGl2RenderingContext::STATIC_DRAW,
-    );
-
-    let position_attribute = gl.get_attrib_location(&program, "position") as u32;
-    gl.enable_vertex_attrib_array(position_attribute);
-    gl.vertex_attrib_pointer_with_i32(
-        position_attribute,
-        2,
-        WebGl2RenderingContext::FLOAT,
-        false,
-        0,
-        0,
-    );
-
-    gl.viewport(0, 0, canvas.width() as i32, canvas.height() as i32);
-    gl.clear_color(0., 0., 0., 1.);
-    gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
-    gl.draw_arrays(WebGl2RenderingContext::LINES, 0, num_spokes as i32 * 2);
-
-    let s = canvas.to_data_url().ok()?;
-    let mut hasher = DefaultHasher::new();
-    hasher.write(s.as_bytes());
-    let hash = hasher.finish();
+/// One `getShaderPrecisionFormat` result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct PrecisionFormat {
+    pub range_min: i32,
+    pub range_max: i32,
+    pub precision: i32,
+}
 
-    Some(hash)
+/// The precision formats of a single shader type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct ShaderPrecisions {
+    pub low_float: PrecisionFormat,
+    pub medium_float: PrecisionFormat,
+    pub high_float: PrecisionFormat,
+    pub low_int: PrecisionFormat,
+    pub medium_int: PrecisionFormat,
+    pub high_int: PrecisionFormat,
+}
+impl ShaderPrecisions {
+    pub fn new<G: WebGlContext>(gl: &G, shader_type: u32) -> Option<Self> {
+        let format = |precision_type| {
+            let format = gl.get_shader_precision_format(shader_type, precision_type)?;
+            Some(PrecisionFormat {
+                range_min: format.range_min(),
+                range_max: format.range_max(),
+                precision: format.precision(),
+            })
+        };
+        Some(Self {
+            low_float: format(WebGl2RenderingContext::LOW_FLOAT)?,
+            medium_float: format(WebGl2RenderingContext::MEDIUM_FLOAT)?,
+            high_float: format(WebGl2RenderingContext::HIGH_FLOAT)?,
+            low_int: format(WebGl2RenderingContext::LOW_INT)?,
+            medium_int: format(WebGl2RenderingContext::MEDIUM_INT)?,
+            high_int: format(WebGl2RenderingContext::HIGH_INT)?,
+        })
+    }
+    /// Looks up a format by its GL precision type constant, i.e. `MEDIUM_FLOAT`.
+    pub fn format(&self, precision_type: u32) -> Option<PrecisionFormat> {
+        match precision_type {
+            WebGl2RenderingContext::LOW_FLOAT => Some(self.low_float),
+            WebGl2RenderingContext::MEDIUM_FLOAT => Some(self.medium_float),
+            WebGl2RenderingContext::HIGH_FLOAT => Some(self.high_float),
+            WebGl2RenderingContext::LOW_INT => Some(self.low_int),
+            WebGl2RenderingContext::MEDIUM_INT => Some(self.medium_int),
+            WebGl2RenderingContext::HIGH_INT => Some(self.high_int),
+            _ => None,
+        }
+    }
+    pub fn formats(&self) -> [PrecisionFormat; 6] {
+        [
+            self.low_float,
+            self.medium_float,
+            self.high_float,
+            self.low_int,
+            self.medium_int,
+            self.high_int,
+        ]
+    }
 }
 
-#[derive(Clone, Debug, Default)]
+/// The full vertex/fragment × low/medium/high float/int precision table.
+/// Mobile GPUs mostly differ in the mediump entries, so we keep all of them rather than just the extremes.
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct ShaderPrecisionFingerPrint {
-    least_min: i32,
-    most_max: i32,
-    highest_precision: i32,
+    vertex: ShaderPrecisions,
+    fragment: ShaderPrecisions,
 }
 impl ShaderPrecisionFingerPrint {
-    pub fn new(gl: &WebGl2RenderingContext) -> Option<Self> {
-        let shader_types = [
-            WebGl2RenderingContext::FRAGMENT_SHADER,
-            WebGl2RenderingContext::VERTEX_SHADER,
-        ];
-        let precision_types = [
-            WebGl2RenderingContext::LOW_FLOAT,
-            WebGl2RenderingContext::MEDIUM_FLOAT,
-            WebGl2RenderingContext::HIGH_FLOAT,
-            WebGl2RenderingContext::LOW_INT,
-            WebGl2RenderingContext::MEDIUM_INT,
-            WebGl2RenderingContext::HIGH_INT,
-        ];
-        let mut least_min = i32::MAX;
-        let mut most_max = i32::MIN;
-        let mut highest_precision = i32::MIN;
-        for shader in shader_types {
-            for precision in precision_types {
-                let shader_format = gl.get_shader_precision_format(shader, precision)?;
-                let precision = shader_format.precision();
-                let range_max = shader_format.range_max();
-                let range_min = shader_format.range_min();
-                least_min = min(least_min, range_min);
-                most_max = max(most_max, range_max);
-                highest_precision = max(precision, highest_precision);
-            }
-        }
+    pub fn new<G: WebGlContext>(gl: &G) -> Option<Self> {
         Some(ShaderPrecisionFingerPrint {
-            least_min,
-            most_max,
-            highest_precision,
+            vertex: ShaderPrecisions::new(gl, WebGl2RenderingContext::VERTEX_SHADER)?,
+            fragment: ShaderPrecisions::new(gl, WebGl2RenderingContext::FRAGMENT_SHADER)?,
         })
     }
+    pub fn vertex(&self) -> &ShaderPrecisions {
+        &self.vertex
+    }
+    pub fn fragment(&self) -> &ShaderPrecisions {
+        &self.fragment
+    }
+    /// Looks up a format by its GL shader type and precision type constants.
+    pub fn format(&self, shader_type: u32, precision_type: u32) -> Option<PrecisionFormat> {
+        match shader_type {
+            WebGl2RenderingContext::VERTEX_SHADER => self.vertex.format(precision_type),
+            WebGl2RenderingContext::FRAGMENT_SHADER => self.fragment.format(precision_type),
+            _ => None,
+        }
+    }
+    fn all_formats(&self) -> impl Iterator<Item = PrecisionFormat> {
+        self.vertex
+            .formats()
+            .into_iter()
+            .chain(self.fragment.formats())
+    }
+    /// The smallest range_min across every shader and precision type.
+    pub fn least_min(&self) -> i32 {
+        self.all_formats().map(|f| f.range_min).fold(i32::MAX, min)
+    }
+    /// The largest range_max across every shader and precision type.
+    pub fn most_max(&self) -> i32 {
+        self.all_formats().map(|f| f.range_max).fold(i32::MIN, max)
+    }
+    /// The largest precision across every shader and precision type.
+    pub fn highest_precision(&self) -> i32 {
+        self.all_formats().map(|f| f.precision).fold(i32::MIN, max)
+    }
 }
 
 #[derive(Debug, PartialEq, Clone, Default)]
@@ -300,7 +645,7 @@ pub struct WebGLParametersFingerPrint {
 }
 
 impl WebGLParametersFingerPrint {
-    pub fn new(gl: &WebGl2RenderingContext) -> Option<Self> {
+    pub fn new<G: WebGlContext>(gl: &G) -> Option<Self> {
         Some(Self {
             active_texture: gl
                 .get_parameter(WebGl2RenderingContext::ACTIVE_TEXTURE)
@@ -0,0 +1,229 @@
+use js_sys::Uint32Array;
+use web_sys::{
+    ExtDisjointTimerQuery, ExtTextureFilterAnisotropic, OvrMultiview2, WebGl2RenderingContext,
+    WebglDrawBuffers,
+};
+
+use super::*;
+
+/// Parameters that only exist once their extension has been enabled.
+/// A field is None when the extension isn't supported by the context.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebGLExtensionParametersFingerPrint {
+    pub max_texture_max_anisotropy_ext: Option<f32>,
+    pub max_draw_buffers_webgl: Option<i32>,
+    pub max_color_attachments_webgl: Option<i32>,
+    pub max_views_ovr: Option<i32>,
+    pub time_elapsed_query_counter_bits: Option<i32>,
+    pub timestamp_query_counter_bits: Option<i32>,
+    pub compressed_texture_formats: Vec<CompressedTextureFormatsFingerPrint>,
+}
+
+/// The formats `COMPRESSED_TEXTURE_FORMATS` gained when enabling a `WEBGL_compressed_texture_*` extension.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CompressedTextureFormatsFingerPrint {
+    pub extension: String,
+    pub formats: Vec<u32>,
+}
+
+impl WebGLExtensionParametersFingerPrint {
+    pub fn new<G: WebGlContext>(gl: &G, supported_extensions: &[String]) -> Self {
+        let supported = |name: &str| supported_extensions.iter().any(|e| e == name);
+
+        let max_texture_max_anisotropy_ext = if supported("EXT_texture_filter_anisotropic") {
+            _ = gl.get_extension("EXT_texture_filter_anisotropic");
+            parameter(
+                gl,
+                ExtTextureFilterAnisotropic::MAX_TEXTURE_MAX_ANISOTROPY_EXT,
+            )
+            .map(|p| p as f32)
+        } else {
+            None
+        };
+
+        let (max_draw_buffers_webgl, max_color_attachments_webgl) =
+            if supported("WEBGL_draw_buffers") {
+                _ = gl.get_extension("WEBGL_draw_buffers");
+                (
+                    parameter(gl, WebglDrawBuffers::MAX_DRAW_BUFFERS_WEBGL).map(|p| p as i32),
+                    parameter(gl, WebglDrawBuffers::MAX_COLOR_ATTACHMENTS_WEBGL).map(|p| p as i32),
+                )
+            } else {
+                (None, None)
+            };
+
+        let max_views_ovr = if supported("OVR_multiview2") {
+            _ = gl.get_extension("OVR_multiview2");
+            parameter(gl, OvrMultiview2::MAX_VIEWS_OVR).map(|p| p as i32)
+        } else {
+            None
+        };
+
+        let (time_elapsed_query_counter_bits, timestamp_query_counter_bits) = [
+            "EXT_disjoint_timer_query",
+            "EXT_disjoint_timer_query_webgl2",
+        ]
+        .into_iter()
+        .filter(|name| supported(name))
+        .find_map(|name| gl.get_extension(name).ok().flatten())
+        .map(|ext| {
+            (
+                query_counter_bits(gl, &ext, ExtDisjointTimerQuery::TIME_ELAPSED_EXT),
+                query_counter_bits(gl, &ext, ExtDisjointTimerQuery::TIMESTAMP_EXT),
+            )
+        })
+        .unwrap_or_default();
+
+        let mut compressed_texture_formats = Vec::new();
+        for extension in supported_extensions
+            .iter()
+            .filter(|e| e.contains("compressed_texture"))
+        {
+            let before = compressed_formats(gl);
+            if gl.get_extension(extension).ok().flatten().is_none() {
+                continue;
+            }
+            let formats = compressed_formats(gl)
+                .into_iter()
+                .filter(|f| !before.contains(f))
+                .collect::<Vec<u32>>();
+            compressed_texture_formats.push(CompressedTextureFormatsFingerPrint {
+                extension: extension.clone(),
+                formats,
+            });
+        }
+
+        Self {
+            max_texture_max_anisotropy_ext,
+            max_draw_buffers_webgl,
+            max_color_attachments_webgl,
+            max_views_ovr,
+            time_elapsed_query_counter_bits,
+            timestamp_query_counter_bits,
+            compressed_texture_formats,
+        }
+    }
+}
+
+/// Limits that only exist on a webgl2 context, which WebGLParametersFingerPrint doesn't collect.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebGL2ParametersFingerPrint {
+    pub max_3d_texture_size: i32,
+    pub max_array_texture_layers: i32,
+    pub max_color_attachments: i32,
+    pub max_combined_fragment_uniform_components: i32,
+    pub max_combined_uniform_blocks: i32,
+    pub max_combined_vertex_uniform_components: i32,
+    pub max_draw_buffers: i32,
+    pub max_element_index: f64,
+    pub max_elements_indices: i32,
+    pub max_elements_vertices: i32,
+    pub max_fragment_input_components: i32,
+    pub max_fragment_uniform_blocks: i32,
+    pub max_fragment_uniform_components: i32,
+    pub max_program_texel_offset: i32,
+    pub max_samples: i32,
+    pub max_server_wait_timeout: f64,
+    pub max_texture_lod_bias: f32,
+    pub max_transform_feedback_interleaved_components: i32,
+    pub max_transform_feedback_separate_attribs: i32,
+    pub max_transform_feedback_separate_components: i32,
+    pub max_uniform_block_size: f64,
+    pub max_uniform_buffer_bindings: i32,
+    pub max_varying_components: i32,
+    pub max_vertex_output_components: i32,
+    pub max_vertex_uniform_blocks: i32,
+    pub max_vertex_uniform_components: i32,
+    pub min_program_texel_offset: i32,
+    pub uniform_buffer_offset_alignment: i32,
+}
+
+impl WebGL2ParametersFingerPrint {
+    pub fn new(gl: &WebGl2RenderingContext) -> Option<Self> {
+        let int = |pname| parameter(gl, pname).map(|p| p as i32);
+        Some(Self {
+            max_3d_texture_size: int(WebGl2RenderingContext::MAX_3D_TEXTURE_SIZE)?,
+            max_array_texture_layers: int(WebGl2RenderingContext::MAX_ARRAY_TEXTURE_LAYERS)?,
+            max_color_attachments: int(WebGl2RenderingContext::MAX_COLOR_ATTACHMENTS)?,
+            max_combined_fragment_uniform_components: int(
+                WebGl2RenderingContext::MAX_COMBINED_FRAGMENT_UNIFORM_COMPONENTS,
+            )?,
+            max_combined_uniform_blocks: int(WebGl2RenderingContext::MAX_COMBINED_UNIFORM_BLOCKS)?,
+            max_combined_vertex_uniform_components: int(
+                WebGl2RenderingContext::MAX_COMBINED_VERTEX_UNIFORM_COMPONENTS,
+            )?,
+            max_draw_buffers: int(WebGl2RenderingContext::MAX_DRAW_BUFFERS)?,
+            max_element_index: parameter(gl, WebGl2RenderingContext::MAX_ELEMENT_INDEX)?,
+            max_elements_indices: int(WebGl2RenderingContext::MAX_ELEMENTS_INDICES)?,
+            max_elements_vertices: int(WebGl2RenderingContext::MAX_ELEMENTS_VERTICES)?,
+            max_fragment_input_components: int(
+                WebGl2RenderingContext::MAX_FRAGMENT_INPUT_COMPONENTS,
+            )?,
+            max_fragment_uniform_blocks: int(WebGl2RenderingContext::MAX_FRAGMENT_UNIFORM_BLOCKS)?,
+            max_fragment_uniform_components: int(
+                WebGl2RenderingContext::MAX_FRAGMENT_UNIFORM_COMPONENTS,
+            )?,
+            max_program_texel_offset: int(WebGl2RenderingContext::MAX_PROGRAM_TEXEL_OFFSET)?,
+            max_samples: int(WebGl2RenderingContext::MAX_SAMPLES)?,
+            max_server_wait_timeout: parameter(
+                gl,
+                WebGl2RenderingContext::MAX_SERVER_WAIT_TIMEOUT,
+            )?,
+            max_texture_lod_bias: parameter(gl, WebGl2RenderingContext::MAX_TEXTURE_LOD_BIAS)?
+                as f32,
+            max_transform_feedback_interleaved_components: int(
+                WebGl2RenderingContext::MAX_TRANSFORM_FEEDBACK_INTERLEAVED_COMPONENTS,
+            )?,
+            max_transform_feedback_separate_attribs: int(
+                WebGl2RenderingContext::MAX_TRANSFORM_FEEDBACK_SEPARATE_ATTRIBS,
+            )?,
+            max_transform_feedback_separate_components: int(
+                WebGl2RenderingContext::MAX_TRANSFORM_FEEDBACK_SEPARATE_COMPONENTS,
+            )?,
+            max_uniform_block_size: parameter(gl, WebGl2RenderingContext::MAX_UNIFORM_BLOCK_SIZE)?,
+            max_uniform_buffer_bindings: int(WebGl2RenderingContext::MAX_UNIFORM_BUFFER_BINDINGS)?,
+            max_varying_components: int(WebGl2RenderingContext::MAX_VARYING_COMPONENTS)?,
+            max_vertex_output_components: int(
+                WebGl2RenderingContext::MAX_VERTEX_OUTPUT_COMPONENTS,
+            )?,
+            max_vertex_uniform_blocks: int(WebGl2RenderingContext::MAX_VERTEX_UNIFORM_BLOCKS)?,
+            max_vertex_uniform_components: int(
+                WebGl2RenderingContext::MAX_VERTEX_UNIFORM_COMPONENTS,
+            )?,
+            min_program_texel_offset: int(WebGl2RenderingContext::MIN_PROGRAM_TEXEL_OFFSET)?,
+            uniform_buffer_offset_alignment: int(
+                WebGl2RenderingContext::UNIFORM_BUFFER_OFFSET_ALIGNMENT,
+            )?,
+        })
+    }
+}
+
+fn parameter<G: WebGlContext>(gl: &G, pname: u32) -> Option<f64> {
+    gl.get_parameter(pname).ok()?.as_f64()
+}
+
+fn compressed_formats<G: WebGlContext>(gl: &G) -> Vec<u32> {
+    gl.get_parameter(WebGl2RenderingContext::COMPRESSED_TEXTURE_FORMATS)
+        .map(|formats| Uint32Array::new(&formats).to_vec())
+        .unwrap_or_default()
+}
+
+fn query_counter_bits<G: WebGlContext>(gl: &G, ext: &Object, target: u32) -> Option<i32> {
+    // webgl1 puts getQueryEXT on the extension object, webgl2 uses getQuery on the context itself.
+    let bits = if Reflect::has(ext, &"getQueryEXT".into()).ok()? {
+        ext.unchecked_ref::<ExtDisjointTimerQuery>()
+            .get_query_ext(target, ExtDisjointTimerQuery::QUERY_COUNTER_BITS_EXT)
+    } else {
+        Reflect::get(gl.as_ref(), &"getQuery".into())
+            .ok()?
+            .dyn_into::<Function>()
+            .ok()?
+            .call2(
+                gl.as_ref(),
+                &target.into(),
+                &ExtDisjointTimerQuery::QUERY_COUNTER_BITS_EXT.into(),
+            )
+            .ok()?
+    };
+    bits.as_f64().map(|b| b as i32)
+}
@@ -0,0 +1,477 @@
+use std::hash::Hasher;
+
+use js_sys::Float32Array;
+use web_sys::{
+    WebGl2RenderingContext, WebGlFramebuffer, WebGlProgram, WebGlRenderbuffer, WebGlTexture,
+};
+
+use super::*;
+
+/// Width and height of the framebuffer every scene is rendered into.
+pub const RENDER_SIZE: i32 = 64;
+
+const VERTEX_SHADER: &str = r#"
+    attribute vec2 position;
+    varying vec2 v_uv;
+    void main() {
+        v_uv = position * 0.5 + 0.5;
+        gl_Position = vec4(position, 0.0, 1.0);
+    }
+"#;
+
+// The fract(sin(..) * 43758.5453) term amplifies every rounding difference, which is the point.
+const GRADIENT_FRAGMENT_SHADER: &str = r#"
+    varying vec2 v_uv;
+    void main() {
+        vec2 p = v_uv * 2.0 - 1.0;
+        float r = length(p);
+        float a = atan(p.y, p.x);
+        vec3 c = vec3(
+            0.5 + 0.5 * sin(a * 7.0 + r * 31.4159),
+            fract(sin(dot(v_uv, vec2(12.9898, 78.233))) * 43758.5453),
+            exp2(-r * 3.7) + log2(1.0 + v_uv.x * v_uv.y * 15.0) / 4.0
+        );
+        gl_FragColor = vec4(pow(c, vec3(1.0 / 2.2)), 1.0);
+    }
+"#;
+
+const TEXTURE_FRAGMENT_SHADER: &str = r#"
+    precision mediump float;
+    varying vec2 v_uv;
+    uniform sampler2D u_texture;
+    void main() {
+        // stretch the coordinates more towards the top, so sampling walks down the mip chain.
+        vec2 uv = vec2(v_uv.x * (1.0 + v_uv.y * 15.0), v_uv.y * 9.0);
+        gl_FragColor = texture2D(u_texture, uv);
+    }
+"#;
+
+const COLOR_FRAGMENT_SHADER: &str = r#"
+    precision mediump float;
+    uniform vec4 u_color;
+    void main() {
+        gl_FragColor = u_color;
+    }
+"#;
+
+const FULLSCREEN_QUAD: [f32; 8] = [-1., -1., 1., -1., -1., 1., 1., 1.];
+
+/// Hashes of scenes rendered into a framebuffer and read back with `readPixels`.
+/// Each scene exercises a different part of the GPU pipeline, so they are hashed separately.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebGLRenderingFingerPrint {
+    pub gradient_highp_hash: Option<u64>,
+    pub gradient_mediump_hash: Option<u64>,
+    pub texture_hash: Option<u64>,
+    pub blending_hash: Option<u64>,
+    /// Only rendered on a webgl2 context.
+    pub msaa_hash: Option<u64>,
+}
+
+impl WebGLRenderingFingerPrint {
+    /// Returns None if we can't create a framebuffer to render into.
+    /// Every GPU object is deleted again before returning, so fingerprinting repeatedly doesn't leak.
+    pub fn new<G: WebGlContext>(gl: &G) -> Option<Self> {
+        let vertex_buffer = gl.create_buffer()?;
+        let Some((framebuffer, target)) = create_render_target(gl) else {
+            gl.delete_buffer(Some(&vertex_buffer));
+            return None;
+        };
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&vertex_buffer));
+        gl.viewport(0, 0, RENDER_SIZE, RENDER_SIZE);
+
+        let gradient_highp_hash = gradient_scene(gl, "highp");
+        let gradient_mediump_hash = gradient_scene(gl, "mediump");
+        let texture_hash = texture_scene(gl);
+        let blending_hash = blending_scene(gl);
+        let msaa_hash = gl
+            .dyn_ref::<WebGl2RenderingContext>()
+            .and_then(|gl| msaa_scene(gl, &target));
+
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+        gl.bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, None);
+        gl.delete_framebuffer(Some(&framebuffer));
+        gl.delete_texture(Some(&target));
+        gl.delete_buffer(Some(&vertex_buffer));
+        Some(Self {
+            gradient_highp_hash,
+            gradient_mediump_hash,
+            texture_hash,
+            blending_hash,
+            msaa_hash,
+        })
+    }
+}
+
+/// Creates an RGBA texture backed framebuffer, leaves it bound and returns it with its texture.
+fn create_render_target<G: WebGlContext>(gl: &G) -> Option<(WebGlFramebuffer, WebGlTexture)> {
+    let texture = gl.create_texture()?;
+    gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+    if gl
+        .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            WebGl2RenderingContext::TEXTURE_2D,
+            0,
+            WebGl2RenderingContext::RGBA as i32,
+            RENDER_SIZE,
+            RENDER_SIZE,
+            0,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            None,
+        )
+        .is_err()
+    {
+        gl.delete_texture(Some(&texture));
+        return None;
+    }
+    gl.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+        WebGl2RenderingContext::NEAREST as i32,
+    );
+    let Some(framebuffer) = gl.create_framebuffer() else {
+        gl.delete_texture(Some(&texture));
+        return None;
+    };
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(&framebuffer));
+    gl.framebuffer_texture_2d(
+        WebGl2RenderingContext::FRAMEBUFFER,
+        WebGl2RenderingContext::COLOR_ATTACHMENT0,
+        WebGl2RenderingContext::TEXTURE_2D,
+        Some(&texture),
+        0,
+    );
+    gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, None);
+    if gl.check_framebuffer_status(WebGl2RenderingContext::FRAMEBUFFER)
+        != WebGl2RenderingContext::FRAMEBUFFER_COMPLETE
+    {
+        gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+        gl.delete_framebuffer(Some(&framebuffer));
+        gl.delete_texture(Some(&texture));
+        return None;
+    }
+    Some((framebuffer, texture))
+}
+
+fn gradient_scene<G: WebGlContext>(gl: &G, precision: &str) -> Option<u64> {
+    // webgl1 fragment shaders don't have to support highp, so fall back rather than fail to compile.
+    let fragment_shader = format!(
+        "#ifdef GL_FRAGMENT_PRECISION_HIGH\nprecision {precision} float;\n#else\nprecision mediump float;\n#endif\n{GRADIENT_FRAGMENT_SHADER}"
+    );
+    let program = create_program(gl, VERTEX_SHADER, &fragment_shader)?;
+    clear(gl);
+    let hash = draw(
+        gl,
+        &program,
+        &FULLSCREEN_QUAD,
+        WebGl2RenderingContext::TRIANGLE_STRIP,
+    )
+    .and_then(|_| read_hash(gl));
+    gl.delete_program(Some(&program));
+    hash
+}
+
+fn texture_scene<G: WebGlContext>(gl: &G) -> Option<u64> {
+    let program = create_program(gl, VERTEX_SHADER, TEXTURE_FRAGMENT_SHADER)?;
+    let size = 64;
+    let mut pixels = Vec::with_capacity(size * size * 4);
+    for y in 0..size {
+        for x in 0..size {
+            let checker = ((x / 4 + y / 4) % 2) as u8 * 255;
+            pixels.extend_from_slice(&[checker, (x * 4) as u8, (y * 4) as u8, 255]);
+        }
+    }
+    let Some(texture) = gl.create_texture() else {
+        gl.delete_program(Some(&program));
+        return None;
+    };
+    let hash = textured_draw(gl, &program, &texture, &pixels, size as i32);
+    gl.delete_texture(Some(&texture));
+    gl.delete_program(Some(&program));
+    hash
+}
+
+fn textured_draw<G: WebGlContext>(
+    gl: &G,
+    program: &WebGlProgram,
+    texture: &WebGlTexture,
+    pixels: &[u8],
+    size: i32,
+) -> Option<u64> {
+    gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(texture));
+    gl.tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+        WebGl2RenderingContext::TEXTURE_2D,
+        0,
+        WebGl2RenderingContext::RGBA as i32,
+        size,
+        size,
+        0,
+        WebGl2RenderingContext::RGBA,
+        WebGl2RenderingContext::UNSIGNED_BYTE,
+        Some(pixels),
+    )
+    .ok()?;
+    gl.generate_mipmap(WebGl2RenderingContext::TEXTURE_2D);
+    gl.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+        WebGl2RenderingContext::LINEAR_MIPMAP_LINEAR as i32,
+    );
+    gl.tex_parameteri(
+        WebGl2RenderingContext::TEXTURE_2D,
+        WebGl2RenderingContext::TEXTURE_MAG_FILTER,
+        WebGl2RenderingContext::LINEAR as i32,
+    );
+    clear(gl);
+    let drawn = draw(
+        gl,
+        program,
+        &FULLSCREEN_QUAD,
+        WebGl2RenderingContext::TRIANGLE_STRIP,
+    );
+    gl.bind_texture(WebGl2RenderingContext::TEXTURE_2D, None);
+    drawn?;
+    read_hash(gl)
+}
+
+fn blending_scene<G: WebGlContext>(gl: &G) -> Option<u64> {
+    let program = create_program(gl, VERTEX_SHADER, COLOR_FRAGMENT_SHADER)?;
+    let triangles: [([f32; 6], [f32; 4]); 3] = [
+        (
+            [-0.9, -0.8, 0.7, -0.3, -0.2, 0.9],
+            [0.812, 0.195, 0.553, 0.5],
+        ),
+        (
+            [-0.6, 0.8, 0.9, 0.6, 0.1, -0.9],
+            [0.195, 0.702, 0.921, 0.35],
+        ),
+        (
+            [-0.8, 0.1, 0.8, -0.7, 0.6, 0.8],
+            [0.953, 0.851, 0.114, 0.65],
+        ),
+    ];
+    clear(gl);
+    gl.enable(WebGl2RenderingContext::BLEND);
+    gl.blend_func(
+        WebGl2RenderingContext::SRC_ALPHA,
+        WebGl2RenderingContext::ONE_MINUS_SRC_ALPHA,
+    );
+    let mut drawn = Some(());
+    for (vertices, [r, g, b, a]) in triangles {
+        gl.use_program(Some(&program));
+        gl.uniform4f(
+            gl.get_uniform_location(&program, "u_color").as_ref(),
+            r,
+            g,
+            b,
+            a,
+        );
+        drawn = drawn.and(draw(
+            gl,
+            &program,
+            &vertices,
+            WebGl2RenderingContext::TRIANGLES,
+        ));
+    }
+    gl.disable(WebGl2RenderingContext::BLEND);
+    let hash = drawn.and_then(|_| read_hash(gl));
+    gl.delete_program(Some(&program));
+    hash
+}
+
+/// Renders into a multisampled renderbuffer and resolves it into `target` with blitFramebuffer.
+/// Where the samples sit inside a pixel is up to the GPU, so the resolved edges differ between them.
+fn msaa_scene(gl: &WebGl2RenderingContext, target: &WebGlTexture) -> Option<u64> {
+    let max_samples = gl
+        .get_parameter(WebGl2RenderingContext::MAX_SAMPLES)
+        .ok()?
+        .as_f64()? as i32;
+    if max_samples < 2 {
+        return None;
+    }
+    let program = create_program(gl, VERTEX_SHADER, COLOR_FRAGMENT_SHADER)?;
+    let renderbuffer = gl.create_renderbuffer();
+    let multisampled = gl.create_framebuffer();
+    let resolved = gl.create_framebuffer();
+    let hash = match (&renderbuffer, &multisampled, &resolved) {
+        (Some(renderbuffer), Some(multisampled), Some(resolved)) => multisampled_draw(
+            gl,
+            &program,
+            target,
+            max_samples,
+            renderbuffer,
+            multisampled,
+            resolved,
+        ),
+        _ => None,
+    };
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, None);
+    gl.delete_framebuffer(resolved.as_ref());
+    gl.delete_framebuffer(multisampled.as_ref());
+    gl.delete_renderbuffer(renderbuffer.as_ref());
+    gl.delete_program(Some(&program));
+    hash
+}
+
+/// Draws into a multisampled renderbuffer and resolves it into `target`.
+fn multisampled_draw(
+    gl: &WebGl2RenderingContext,
+    program: &WebGlProgram,
+    target: &WebGlTexture,
+    max_samples: i32,
+    renderbuffer: &WebGlRenderbuffer,
+    multisampled: &WebGlFramebuffer,
+    resolved: &WebGlFramebuffer,
+) -> Option<u64> {
+    gl.bind_renderbuffer(WebGl2RenderingContext::RENDERBUFFER, Some(renderbuffer));
+    gl.renderbuffer_storage_multisample(
+        WebGl2RenderingContext::RENDERBUFFER,
+        max_samples.min(4),
+        WebGl2RenderingContext::RGBA8,
+        RENDER_SIZE,
+        RENDER_SIZE,
+    );
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(multisampled));
+    gl.framebuffer_renderbuffer(
+        WebGl2RenderingContext::FRAMEBUFFER,
+        WebGl2RenderingContext::COLOR_ATTACHMENT0,
+        WebGl2RenderingContext::RENDERBUFFER,
+        Some(renderbuffer),
+    );
+    if gl.check_framebuffer_status(WebGl2RenderingContext::FRAMEBUFFER)
+        != WebGl2RenderingContext::FRAMEBUFFER_COMPLETE
+    {
+        return None;
+    }
+
+    // thin spokes, so almost every covered pixel is an edge pixel.
+    let spokes = 23;
+    let mut vertices = Vec::with_capacity(spokes * 6);
+    for i in 0..spokes {
+        let angle = i as f32 * std::f32::consts::TAU / spokes as f32;
+        let (sin, cos) = angle.sin_cos();
+        let (sin_2, cos_2) = (angle + 0.05).sin_cos();
+        vertices.extend_from_slice(&[0., 0., cos, sin, cos_2, sin_2]);
+    }
+    clear(gl);
+    gl.use_program(Some(program));
+    gl.uniform4f(
+        gl.get_uniform_location(program, "u_color").as_ref(),
+        0.812,
+        0.195,
+        0.553,
+        1.,
+    );
+    draw(gl, program, &vertices, WebGl2RenderingContext::TRIANGLES)?;
+
+    gl.bind_framebuffer(WebGl2RenderingContext::DRAW_FRAMEBUFFER, Some(resolved));
+    gl.framebuffer_texture_2d(
+        WebGl2RenderingContext::DRAW_FRAMEBUFFER,
+        WebGl2RenderingContext::COLOR_ATTACHMENT0,
+        WebGl2RenderingContext::TEXTURE_2D,
+        Some(target),
+        0,
+    );
+    gl.bind_framebuffer(WebGl2RenderingContext::READ_FRAMEBUFFER, Some(multisampled));
+    gl.blit_framebuffer(
+        0,
+        0,
+        RENDER_SIZE,
+        RENDER_SIZE,
+        0,
+        0,
+        RENDER_SIZE,
+        RENDER_SIZE,
+        WebGl2RenderingContext::COLOR_BUFFER_BIT,
+        WebGl2RenderingContext::NEAREST,
+    );
+    gl.bind_framebuffer(WebGl2RenderingContext::FRAMEBUFFER, Some(resolved));
+    read_hash(gl)
+}
+
+fn create_program<G: WebGlContext>(
+    gl: &G,
+    vertex_shader_src: &str,
+    fragment_shader_src: &str,
+) -> Option<WebGlProgram> {
+    let vertex_shader = gl.create_shader(WebGl2RenderingContext::VERTEX_SHADER)?;
+    let fragment_shader = gl.create_shader(WebGl2RenderingContext::FRAGMENT_SHADER)?;
+    gl.shader_source(&vertex_shader, vertex_shader_src);
+    gl.shader_source(&fragment_shader, fragment_shader_src);
+    gl.compile_shader(&vertex_shader);
+    gl.compile_shader(&fragment_shader);
+
+    let program = gl.create_program()?;
+    gl.attach_shader(&program, &vertex_shader);
+    gl.attach_shader(&program, &fragment_shader);
+    gl.link_program(&program);
+    // the linked program keeps its own copy, the shaders are only flagged while attached.
+    gl.delete_shader(Some(&vertex_shader));
+    gl.delete_shader(Some(&fragment_shader));
+    if gl
+        .get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS)
+        .as_bool()
+        != Some(true)
+    {
+        gl.delete_program(Some(&program));
+        return None;
+    }
+    Some(program)
+}
+
+fn clear<G: WebGlContext>(gl: &G) {
+    gl.clear_color(0., 0., 0., 1.);
+    gl.clear(WebGl2RenderingContext::COLOR_BUFFER_BIT);
+}
+
+fn draw<G: WebGlContext>(
+    gl: &G,
+    program: &WebGlProgram,
+    vertices: &[f32],
+    mode: u32,
+) -> Option<()> {
+    gl.use_program(Some(program));
+    // uploads into the vertex buffer `WebGLRenderingFingerPrint::new` bound.
+    gl.buffer_data_with_array_buffer_view(
+        WebGl2RenderingContext::ARRAY_BUFFER,
+        Float32Array::from(vertices).as_ref(),
+        WebGl2RenderingContext::STATIC_DRAW,
+    );
+    let position_attribute = u32::try_from(gl.get_attrib_location(program, "position")).ok()?;
+    gl.enable_vertex_attrib_array(position_attribute);
+    gl.vertex_attrib_pointer_with_i32(
+        position_attribute,
+        2,
+        WebGl2RenderingContext::FLOAT,
+        false,
+        0,
+        0,
+    );
+    gl.draw_arrays(mode, 0, vertices.len() as i32 / 2);
+    Some(())
+}
+
+/// Hashes the bound framebuffer. Reads it twice so browsers that add noise to readPixels get None, like the canvas fingerprint.
+fn read_hash<G: WebGlContext>(gl: &G) -> Option<u64> {
+    let read = || {
+        let mut pixels = vec![0u8; (RENDER_SIZE * RENDER_SIZE * 4) as usize];
+        gl.read_pixels_with_opt_u8_array(
+            0,
+            0,
+            RENDER_SIZE,
+            RENDER_SIZE,
+            WebGl2RenderingContext::RGBA,
+            WebGl2RenderingContext::UNSIGNED_BYTE,
+            Some(&mut pixels),
+        )
+        .ok()?;
+        Some(pixels)
+    };
+    let pixels = read()?;
+    if read()? != pixels {
+        return None;
+    }
+    let mut hasher = FnvHasher::default();
+    hasher.write(&pixels);
+    Some(hasher.finish())
+}
@@ -0,0 +1,114 @@
+use js_sys::Promise;
+
+use super::*;
+
+/// WebGPU adapter details. web_sys only binds the WebGPU types behind `web_sys_unstable_apis`,
+/// so everything here goes through Reflect instead.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WebGpuFingerPrint {
+    pub preferred_canvas_format: Option<String>,
+    pub wgsl_language_features: Vec<String>,
+    pub vendor: Option<String>,
+    pub architecture: Option<String>,
+    pub device: Option<String>,
+    pub description: Option<String>,
+    pub is_fallback_adapter: bool,
+    /// Sorted, since the browser's iteration order isn't part of the fingerprint.
+    pub features: Vec<String>,
+    /// Sorted by limit name.
+    pub limits: Vec<(String, f64)>,
+}
+
+impl WebGpuFingerPrint {
+    /// Unsupported if there's no `navigator.gpu`, or it won't give us an adapter, which is the norm on headless machines.
+    pub async fn new(navigator: &Navigator) -> Support<Self> {
+        Self::from_navigator(navigator).await.into()
+    }
+
+    async fn from_navigator(navigator: &Navigator) -> Option<Self> {
+        let gpu = Reflect::get(navigator, &"gpu".into()).ok()?;
+        if gpu.is_undefined() || gpu.is_null() {
+            return None;
+        }
+        let preferred_canvas_format = call_method(&gpu, "getPreferredCanvasFormat", &Array::new())
+            .and_then(|f| f.as_string());
+        let wgsl_language_features = Reflect::get(&gpu, &"wgslLanguageFeatures".into())
+            .ok()
+            .map(|set| sorted_strings(&set))
+            .unwrap_or_default();
+
+        let promise = call_method(&gpu, "requestAdapter", &Array::new())?
+            .dyn_into::<Promise>()
+            .ok()?;
+        let adapter = JsFuture::from(promise).await.ok()?;
+        if adapter.is_null() || adapter.is_undefined() {
+            return None;
+        }
+        let info = adapter_info(&adapter).await.unwrap_or(JsValue::UNDEFINED);
+        let string = |key: &str| {
+            Reflect::get(&info, &key.into())
+                .ok()
+                .and_then(|v| v.as_string())
+        };
+        // isFallbackAdapter moved from the adapter onto its info.
+        let is_fallback_adapter = [&info, &adapter]
+            .into_iter()
+            .find_map(|o| Reflect::get(o, &"isFallbackAdapter".into()).ok()?.as_bool())
+            .unwrap_or_default();
+        let features = Reflect::get(&adapter, &"features".into())
+            .ok()
+            .map(|set| sorted_strings(&set))
+            .unwrap_or_default();
+        let limits = Reflect::get(&adapter, &"limits".into())
+            .ok()
+            .map(|limits| {
+                let mut limits = Object::keys(&Object::get_prototype_of(&limits))
+                    .into_iter()
+                    .filter_map(|key| {
+                        let value = Reflect::get(&limits, &key).ok()?.as_f64()?;
+                        Some((key.as_string()?, value))
+                    })
+                    .collect::<Vec<(String, f64)>>();
+                limits.sort_by(|a, b| a.0.cmp(&b.0));
+                limits
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            preferred_canvas_format,
+            wgsl_language_features,
+            vendor: string("vendor"),
+            architecture: string("architecture"),
+            device: string("device"),
+            description: string("description"),
+            is_fallback_adapter,
+            features,
+            limits,
+        })
+    }
+}
+
+/// `adapter.info` on current browsers, `adapter.requestAdapterInfo()` on the ones that shipped WebGPU first.
+async fn adapter_info(adapter: &JsValue) -> Option<JsValue> {
+    let info = Reflect::get(adapter, &"info".into()).ok()?;
+    if !info.is_undefined() {
+        return Some(info);
+    }
+    let promise = call_method(adapter, "requestAdapterInfo", &Array::new())?
+        .dyn_into::<Promise>()
+        .ok()?;
+    JsFuture::from(promise).await.ok()
+}
+
+/// Collects a setlike of strings, i.e. GPUSupportedFeatures.
+fn sorted_strings(set: &JsValue) -> Vec<String> {
+    if set.is_undefined() || set.is_null() {
+        return Vec::new();
+    }
+    let mut strings = Array::from(set)
+        .into_iter()
+        .filter_map(|s| s.as_string())
+        .collect::<Vec<String>>();
+    strings.sort();
+    strings
+}
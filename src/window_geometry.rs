@@ -0,0 +1,90 @@
+use super::*;
+
+/// Zoom levels chrome and firefox offer from the menu, so an estimate can be snapped to one of them.
+pub static ZOOM_LEVELS: [f64; 17] = [
+    0.25, 0.33, 0.5, 0.67, 0.75, 0.8, 0.9, 1.0, 1.1, 1.25, 1.5, 1.75, 2.0, 2.5, 3.0, 4.0, 5.0,
+];
+
+/// Tor Browser and firefox's resistFingerprinting round the content area down to these steps.
+pub const LETTERBOX_WIDTH_STEP: i32 = 200;
+pub const LETTERBOX_HEIGHT_STEP: i32 = 100;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WindowGeometryFingerPrint {
+    pub inner_width: i32,
+    pub inner_height: i32,
+    pub outer_width: i32,
+    pub outer_height: i32,
+    pub screen_x: i32,
+    pub screen_y: i32,
+    pub visual_viewport_scale: Option<f64>,
+    pub device_pixel_ratio: f64,
+    /// `screen.width * devicePixelRatio`, whole numbers on an unzoomed page.
+    /// Fractional values mean the page is zoomed or the ratio is spoofed.
+    pub physical_screen_width: f64,
+    pub physical_screen_height: f64,
+}
+
+/// How much of each screen edge the OS keeps for itself, i.e. the taskbar, dock or menu bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ScreenInsets {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+impl WindowGeometryFingerPrint {
+    pub fn new(window: &Window, screen: &ScreenFingerPrint) -> Option<Self> {
+        let int = |value: Result<JsValue, JsValue>| value.ok()?.as_f64().map(|v| v as i32);
+        let device_pixel_ratio = window.device_pixel_ratio();
+        Some(Self {
+            inner_width: int(window.inner_width())?,
+            inner_height: int(window.inner_height())?,
+            outer_width: int(window.outer_width())?,
+            outer_height: int(window.outer_height())?,
+            screen_x: int(window.screen_x()).unwrap_or_default(),
+            screen_y: int(window.screen_y()).unwrap_or_default(),
+            visual_viewport_scale: window.visual_viewport().map(|v| v.scale()),
+            device_pixel_ratio,
+            physical_screen_width: screen.width as f64 * device_pixel_ratio,
+            physical_screen_height: screen.height as f64 * device_pixel_ratio,
+        })
+    }
+
+    /// Estimates the page zoom from the outer width, which chrome keeps in unzoomed pixels, against the zoomed inner width.
+    /// Firefox and Safari zoom the outer width too, so this is None for any `engine` but Blink, see
+    /// `FeatureDetectionFingerPrint::detect`. Also None if the ratio isn't close to a menu zoom level, e.g. with devtools
+    /// docked to the side.
+    pub fn zoom(&self, engine: Engine) -> Option<f64> {
+        if engine != Engine::Blink || self.inner_width <= 0 || self.outer_width <= 0 {
+            return None;
+        }
+        let ratio = self.outer_width as f64 / self.inner_width as f64;
+        ZOOM_LEVELS
+            .iter()
+            .copied()
+            .min_by(|a, b| (a - ratio).abs().total_cmp(&(b - ratio).abs()))
+            .filter(|level| (level - ratio).abs() / level < 0.03)
+    }
+
+    /// Whether the content area has been rounded the way Tor Browser and resistFingerprinting do.
+    pub fn letterboxed(&self) -> bool {
+        self.inner_width > 0
+            && self.inner_height > 0
+            && self.inner_width % LETTERBOX_WIDTH_STEP == 0
+            && self.inner_height % LETTERBOX_HEIGHT_STEP == 0
+    }
+}
+
+impl ScreenInsets {
+    /// A mac reports the menu bar at the top and the dock at the bottom, windows usually only the bottom.
+    pub fn new(screen: &ScreenFingerPrint) -> Self {
+        Self {
+            top: screen.avail_top.max(0),
+            right: (screen.width - screen.avail_left - screen.avail_width).max(0),
+            bottom: (screen.height - screen.avail_top - screen.avail_height).max(0),
+            left: screen.avail_left.max(0),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use fingerprint_rs::*;
+
+#[test]
+fn test_verdict_definite_signals() {
+    assert_eq!(BotSignals::default().verdict(), BotVerdict::Human);
+    let webdriver = BotSignals {
+        webdriver: true,
+        ..Default::default()
+    };
+    assert_eq!(webdriver.verdict(), BotVerdict::Bot);
+    let chromedriver = BotSignals {
+        automation_globals: vec!["cdc_adoQpoasnfa76pfcZLmcfl_Array".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(chromedriver.verdict(), BotVerdict::Bot);
+    let puppeteer = BotSignals {
+        automation_stack_markers: vec!["pptr:".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(puppeteer.verdict(), BotVerdict::Bot);
+    let headless = BotSignals {
+        notification_permission_mismatch: true,
+        ..Default::default()
+    };
+    assert_eq!(headless.verdict(), BotVerdict::Bot);
+}
+
+#[test]
+fn test_verdict_circumstantial_signals() {
+    // a VM without a GPU is a real user often enough.
+    let vm = BotSignals {
+        software_renderer: true,
+        ..Default::default()
+    };
+    assert_eq!(vm.verdict(), BotVerdict::Human);
+    // devtools open on a real machine looks like a DevTools protocol client.
+    let devtools = BotSignals {
+        devtools_protocol: true,
+        ..Default::default()
+    };
+    assert_eq!(devtools.verdict(), BotVerdict::Human);
+    assert_eq!(
+        BotSignals {
+            software_renderer: true,
+            ..devtools
+        }
+        .verdict(),
+        BotVerdict::Suspicious
+    );
+    let vm_without_taskbar = BotSignals {
+        screen_equals_avail: true,
+        ..vm.clone()
+    };
+    assert_eq!(vm_without_taskbar.verdict(), BotVerdict::Suspicious);
+    let stripped = BotSignals {
+        no_plugins: true,
+        ..vm_without_taskbar
+    };
+    assert_eq!(stripped.verdict(), BotVerdict::Bot);
+}
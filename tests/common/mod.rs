@@ -0,0 +1,47 @@
+//! Fixtures shared by the native tests. Each test binary only uses some of them.
+#![allow(dead_code)]
+
+use fingerprint_rs::*;
+
+pub const WINDOWS_CHROME: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+/// Chrome 110+ reduces the Android version and model to "Android 10; K".
+pub const ANDROID_CHROME: &str = "Mozilla/5.0 (Linux; Android 10; K) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36";
+pub const ANDROID_WEBVIEW: &str = "Mozilla/5.0 (Linux; Android 14; Pixel 8 Build/AP1A.240305.019; wv) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/124.0.6367.82 Mobile Safari/537.36";
+pub const MAC_SAFARI: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15";
+pub const HEADLESS_CHROME: &str = "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) HeadlessChrome/120.0.6099.109 Safari/537.36";
+
+pub const NVIDIA_D3D11_RENDERER: &str =
+    "ANGLE (NVIDIA, NVIDIA GeForce RTX 3070 Direct3D11 vs_5_0 ps_5_0, D3D11)";
+pub const APPLE_METAL_RENDERER: &str =
+    "ANGLE (Apple, ANGLE Metal Renderer: Apple M1 Pro, Unspecified Version)";
+
+/// A fingerprint with nothing but the navigator filled in.
+pub fn finger_print(navigator_finger_print: NavigatorFingerPrint) -> FingerPrint {
+    FingerPrint {
+        window_finger_print: Some(WindowFingerPrint {
+            navigator_finger_print,
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Gives `finger_print` a WebGL2 context with `renderer` as its unmasked renderer.
+pub fn set_renderer(finger_print: &mut FingerPrint, renderer: &str) {
+    finger_print.webgl_finger_print = Some(WebGLFingerPrint {
+        webgl2: Some(WebGLContextFingerPrint {
+            renderer: Some(renderer.to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+}
+
+/// The checks `inconsistencies` flagged, with their severities.
+pub fn findings(finger_print: &FingerPrint) -> Vec<(&'static str, Severity)> {
+    finger_print
+        .inconsistencies()
+        .iter()
+        .map(|i| (i.check, i.severity))
+        .collect()
+}
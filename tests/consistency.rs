@@ -0,0 +1,226 @@
+mod common;
+
+use common::*;
+use fingerprint_rs::*;
+
+#[test]
+fn test_consistent() {
+    let mut finger_print = finger_print(NavigatorFingerPrint {
+        user_agent: WINDOWS_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        language: "de-DE".to_string(),
+        hardware_concurrency: 16.0,
+        device_memory: Some(8.0),
+        ..Default::default()
+    });
+    set_renderer(&mut finger_print, NVIDIA_D3D11_RENDERER);
+    let window = finger_print.window_finger_print.as_mut().unwrap();
+    window.fonts = Some(vec!["Arial".to_string(), "Segoe UI".to_string()]);
+    window.intl_finger_print = Some(IntlFingerPrint {
+        locale: Some("de-DE".to_string()),
+        time_zone: Some("Europe/Berlin".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(finger_print.inconsistencies(), vec![]);
+    assert_eq!(FingerPrint::default().inconsistencies(), vec![]);
+}
+
+#[test]
+fn test_platform_and_touch() {
+    let spoofed = finger_print(NavigatorFingerPrint {
+        user_agent: ANDROID_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        max_touch_points: 0,
+        hardware_concurrency: 32.0,
+        ..Default::default()
+    });
+    assert_eq!(
+        findings(&spoofed),
+        vec![
+            ("user_agent_platform", Severity::High),
+            ("touch_points", Severity::High),
+            ("hardware_concurrency", Severity::Medium),
+        ]
+    );
+    let details = spoofed
+        .inconsistencies()
+        .into_iter()
+        .map(|i| i.detail)
+        .collect::<Vec<String>>();
+    assert_eq!(
+        details,
+        vec![
+            "UA says Android but platform is \"Win32\"",
+            "Mobile UA without touch points",
+            "32 cores on a Android Mobile UA",
+        ]
+    );
+
+    // Android reports a Linux platform.
+    let android = finger_print(NavigatorFingerPrint {
+        user_agent: ANDROID_CHROME.to_string(),
+        platform: "Linux armv81".to_string(),
+        max_touch_points: 5,
+        hardware_concurrency: 8.0,
+        ..Default::default()
+    });
+    assert_eq!(findings(&android), vec![]);
+}
+
+#[test]
+fn test_gpu_os() {
+    let mut apple_gpu = finger_print(NavigatorFingerPrint {
+        user_agent: WINDOWS_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        ..Default::default()
+    });
+    set_renderer(&mut apple_gpu, APPLE_METAL_RENDERER);
+    let details = apple_gpu
+        .inconsistencies()
+        .into_iter()
+        .map(|i| (i.check, i.severity, i.detail))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        details,
+        vec![
+            (
+                "gpu_os",
+                Severity::High,
+                "Apple GPU on a Windows UA".to_string()
+            ),
+            (
+                "gpu_os",
+                Severity::High,
+                "Metal backend on a Windows UA".to_string()
+            ),
+        ]
+    );
+
+    let mut direct3d = finger_print(NavigatorFingerPrint {
+        user_agent: MAC_SAFARI.to_string(),
+        platform: "MacIntel".to_string(),
+        ..Default::default()
+    });
+    set_renderer(&mut direct3d, NVIDIA_D3D11_RENDERER);
+    let inconsistencies = direct3d.inconsistencies();
+    assert_eq!(inconsistencies.len(), 1);
+    assert_eq!(inconsistencies[0].severity, Severity::High);
+    assert_eq!(inconsistencies[0].detail, "D3D11 backend on a MacOs UA");
+}
+
+#[test]
+fn test_fonts_and_locale() {
+    let mut finger_print = finger_print(NavigatorFingerPrint {
+        user_agent: WINDOWS_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        language: "ja-JP".to_string(),
+        ..Default::default()
+    });
+    let window = finger_print.window_finger_print.as_mut().unwrap();
+    window.fonts = Some(vec!["Geneva".to_string(), "Optima".to_string()]);
+    window.intl_finger_print = Some(IntlFingerPrint {
+        locale: Some("en-US".to_string()),
+        time_zone: Some("America/New_York".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(
+        findings(&finger_print),
+        vec![
+            ("fonts_os", Severity::Medium),
+            ("intl_locale_language", Severity::Low),
+            ("time_zone_language", Severity::Low),
+        ]
+    );
+    assert_eq!(
+        finger_print.inconsistencies()[0].detail,
+        "Geneva is a MacOs font on a Windows UA"
+    );
+
+    // en-US says nothing about where the user is.
+    let window = finger_print.window_finger_print.as_mut().unwrap();
+    window.navigator_finger_print.language = "en-US".to_string();
+    window.fonts = None;
+    assert_eq!(findings(&finger_print), vec![]);
+}
+
+#[test]
+fn test_permissions_and_network() {
+    let spoofed = finger_print(NavigatorFingerPrint {
+        user_agent: MAC_SAFARI.to_string(),
+        platform: "MacIntel".to_string(),
+        permission_fingerprint: Some(PermissionFingerPrint {
+            accelerometer: Some(0),
+            ..Default::default()
+        }),
+        network_information: Some(NetworkInformationFingerPrint {
+            type_: Some("wifi".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    assert_eq!(
+        findings(&spoofed),
+        vec![
+            ("user_agent_permissions", Severity::Medium),
+            ("network_type", Severity::Medium),
+        ]
+    );
+    assert_eq!(
+        spoofed.inconsistencies()[0].detail,
+        "WebKit UA accepts the Blink only permission accelerometer"
+    );
+
+    let chrome = finger_print(NavigatorFingerPrint {
+        user_agent: WINDOWS_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        permission_fingerprint: Some(PermissionFingerPrint {
+            accelerometer: Some(0),
+            ..Default::default()
+        }),
+        network_information: Some(NetworkInformationFingerPrint {
+            effective_type: Some("4g".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    assert_eq!(findings(&chrome), vec![]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_signals_round_trip() {
+    let mut spoofed = finger_print(NavigatorFingerPrint {
+        user_agent: ANDROID_CHROME.to_string(),
+        platform: "Win32".to_string(),
+        language: "ja-JP".to_string(),
+        permission_fingerprint: Some(PermissionFingerPrint {
+            accelerometer: Some(0),
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    set_renderer(&mut spoofed, APPLE_METAL_RENDERER);
+    let window = spoofed.window_finger_print.as_mut().unwrap();
+    window.fonts = Some(vec!["Segoe UI".to_string()]);
+    window.intl_finger_print = Some(IntlFingerPrint {
+        time_zone: Some("America/New_York".to_string()),
+        ..Default::default()
+    });
+
+    let signals = ConsistencySignals::new(&spoofed).unwrap();
+    let submitted = serde_json::to_string(&signals).unwrap();
+    let received: ConsistencySignals = serde_json::from_str(&submitted).unwrap();
+    assert_eq!(received, signals);
+    assert_eq!(received.inconsistencies(), spoofed.inconsistencies());
+    assert_eq!(
+        findings(&spoofed),
+        vec![
+            ("user_agent_platform", Severity::High),
+            ("gpu_os", Severity::High),
+            ("gpu_os", Severity::High),
+            ("touch_points", Severity::High),
+            ("fonts_os", Severity::Medium),
+            ("time_zone_language", Severity::Low),
+        ]
+    );
+}
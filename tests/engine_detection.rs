@@ -0,0 +1,75 @@
+use fingerprint_rs::*;
+
+/// What a browser of `engine` at `version` would report.
+fn features_of(engine: Engine, version: EngineVersion) -> FeatureDetectionFingerPrint {
+    let present = ENGINE_MARKERS
+        .iter()
+        .filter(|(e, _)| *e == engine)
+        .map(|(_, probe)| probe.name())
+        .chain(
+            FEATURE_RELEASES
+                .iter()
+                .filter(|f| f.release(engine).is_some_and(|r| r <= version))
+                .map(|f| f.probe.name()),
+        )
+        .collect();
+    FeatureDetectionFingerPrint { present }
+}
+
+fn version(major: u32, minor: u32) -> EngineVersion {
+    EngineVersion { major, minor }
+}
+
+#[test]
+fn test_detect_blink_range() {
+    let detection = features_of(Engine::Blink, version(121, 0)).detect();
+    assert_eq!(detection.engine, Engine::Blink);
+    assert_eq!(detection.min_version, Some(version(121, 0)));
+    assert_eq!(detection.max_version, Some(version(122, 0)));
+    assert_eq!(detection.to_string(), "Blink >= 121, < 122");
+}
+
+#[test]
+fn test_detect_gecko_and_webkit() {
+    let gecko = features_of(Engine::Gecko, version(115, 0)).detect();
+    assert_eq!(gecko.engine, Engine::Gecko);
+    assert_eq!(gecko.to_string(), "Gecko >= 115, < 119");
+
+    let webkit = features_of(Engine::WebKit, version(17, 4)).detect();
+    assert_eq!(webkit.engine, Engine::WebKit);
+    assert_eq!(webkit.to_string(), "WebKit >= 17.4, < 17.5");
+}
+
+#[test]
+fn test_detect_unknown_without_markers() {
+    let detection = FeatureDetectionFingerPrint::default().detect();
+    assert_eq!(detection, EngineDetection::default());
+    assert_eq!(
+        detection.matches_user_agent(&UserAgent::parse("curl/8.4.0")),
+        None
+    );
+}
+
+#[test]
+fn test_matches_user_agent() {
+    let detection = features_of(Engine::Blink, version(120, 0)).detect();
+    let chrome_120 = UserAgent::parse(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+    );
+    assert_eq!(detection.matches_user_agent(&chrome_120), Some(true));
+    let chrome_131 = UserAgent::parse(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/131.0.0.0 Safari/537.36",
+    );
+    assert_eq!(detection.matches_user_agent(&chrome_131), Some(false));
+    let safari = UserAgent::parse(
+        "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.2 Safari/605.1.15",
+    );
+    assert_eq!(detection.matches_user_agent(&safari), Some(false));
+
+    let webkit = features_of(Engine::WebKit, version(17, 2)).detect();
+    assert_eq!(webkit.matches_user_agent(&safari), Some(true));
+    let ios_chrome = UserAgent::parse(
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.6099.119 Mobile/15E148 Safari/604.1",
+    );
+    assert_eq!(webkit.matches_user_agent(&ios_chrome), Some(true));
+}
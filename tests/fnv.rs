@@ -0,0 +1,24 @@
+use std::hash::{Hash, Hasher};
+
+use fingerprint_rs::*;
+
+#[test]
+fn test_fnv_reference_values() {
+    let hash = |bytes: &[u8]| {
+        let mut hasher = FnvHasher::default();
+        hasher.write(bytes);
+        hasher.finish()
+    };
+    assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+    assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+}
+
+#[test]
+fn test_fnv_usize_is_fixed_width() {
+    let mut usize_hasher = FnvHasher::default();
+    3usize.hash(&mut usize_hasher);
+    let mut u64_hasher = FnvHasher::default();
+    3u64.hash(&mut u64_hasher);
+    assert_eq!(usize_hasher.finish(), u64_hasher.finish());
+}
@@ -0,0 +1,244 @@
+mod common;
+
+use common::*;
+use fingerprint_rs::*;
+
+fn identity(
+    vendor: GpuVendor,
+    model_family: Option<&str>,
+    model: Option<&str>,
+    backend: GraphicsBackend,
+    angle: bool,
+    software: bool,
+) -> GpuIdentity {
+    GpuIdentity {
+        vendor,
+        model_family: model_family.map(str::to_string),
+        model: model.map(str::to_string),
+        backend,
+        angle,
+        software,
+    }
+}
+
+#[test]
+fn test_parse_angle_d3d11() {
+    assert_eq!(
+        GpuIdentity::parse(NVIDIA_D3D11_RENDERER),
+        identity(
+            GpuVendor::Nvidia,
+            Some("GeForce RTX 3000"),
+            Some("GeForce RTX 3070"),
+            GraphicsBackend::D3D11,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (AMD, AMD Radeon RX 6800 XT Direct3D11 vs_5_0 ps_5_0, D3D11)"),
+        identity(
+            GpuVendor::Amd,
+            Some("Radeon RX 6000"),
+            Some("Radeon RX 6800 XT"),
+            GraphicsBackend::D3D11,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse(
+            "ANGLE (Intel, Intel(R) Iris(R) Xe Graphics Direct3D11 vs_5_0 ps_5_0, D3D11)"
+        ),
+        identity(
+            GpuVendor::Intel,
+            Some("Iris Xe Graphics"),
+            Some("Iris Xe Graphics"),
+            GraphicsBackend::D3D11,
+            true,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_parse_old_angle() {
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (NVIDIA GeForce GTX 1060 6GB Direct3D11 vs_5_0 ps_5_0)"),
+        identity(
+            GpuVendor::Nvidia,
+            Some("GeForce GTX 1000"),
+            Some("GeForce GTX 1060 6GB"),
+            GraphicsBackend::D3D11,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (Intel(R) HD Graphics Direct3D9Ex vs_3_0 ps_3_0)"),
+        identity(
+            GpuVendor::Intel,
+            Some("HD Graphics"),
+            Some("HD Graphics"),
+            GraphicsBackend::D3D9,
+            true,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_parse_metal() {
+    assert_eq!(
+        GpuIdentity::parse(APPLE_METAL_RENDERER),
+        identity(
+            GpuVendor::Apple,
+            Some("M1"),
+            Some("M1 Pro"),
+            GraphicsBackend::Metal,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse("Apple GPU"),
+        identity(
+            GpuVendor::Apple,
+            None,
+            None,
+            GraphicsBackend::Unknown,
+            false,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_parse_opengl_and_vulkan() {
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (Intel, Mesa Intel(R) UHD Graphics 620 (KBL GT2), OpenGL 4.6)"),
+        identity(
+            GpuVendor::Intel,
+            Some("UHD Graphics 600"),
+            Some("UHD Graphics 620"),
+            GraphicsBackend::OpenGL,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse(
+            "ANGLE (NVIDIA Corporation, NVIDIA GeForce RTX 3080/PCIe/SSE2, OpenGL 4.5.0)"
+        ),
+        identity(
+            GpuVendor::Nvidia,
+            Some("GeForce RTX 3000"),
+            Some("GeForce RTX 3080"),
+            GraphicsBackend::OpenGL,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse(
+            "ANGLE (NVIDIA, Vulkan 1.3.242 (NVIDIA GeForce RTX 3060 (0x00002503)), NVIDIA)"
+        ),
+        identity(
+            GpuVendor::Nvidia,
+            Some("GeForce RTX 3000"),
+            Some("GeForce RTX 3060"),
+            GraphicsBackend::Vulkan,
+            true,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_parse_mobile() {
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (Qualcomm, Adreno (TM) 650, OpenGL ES 3.2)"),
+        identity(
+            GpuVendor::Qualcomm,
+            Some("Adreno 600"),
+            Some("Adreno 650"),
+            GraphicsBackend::OpenGLES,
+            true,
+            false
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse("Mali-G78 MP24"),
+        identity(
+            GpuVendor::Arm,
+            Some("Mali-G"),
+            Some("Mali-G78 MP24"),
+            GraphicsBackend::Unknown,
+            false,
+            false
+        )
+    );
+}
+
+#[test]
+fn test_parse_software() {
+    assert_eq!(
+        GpuIdentity::parse(
+            "ANGLE (Google, Vulkan 1.3.0 (SwiftShader Device (Subzero) (0x0000C0DE)), SwiftShader driver)"
+        ),
+        identity(
+            GpuVendor::Google,
+            Some("SwiftShader"),
+            Some("SwiftShader"),
+            GraphicsBackend::Vulkan,
+            true,
+            true
+        )
+    );
+    assert_eq!(
+        GpuIdentity::parse("ANGLE (Mesa, llvmpipe (LLVM 15.0.7, 256 bits), OpenGL 4.5)"),
+        identity(
+            GpuVendor::Mesa,
+            Some("llvmpipe"),
+            Some("llvmpipe"),
+            GraphicsBackend::OpenGL,
+            true,
+            true
+        )
+    );
+    assert!(
+        GpuIdentity::parse(
+            "ANGLE (Microsoft, Microsoft Basic Render Driver Direct3D11 vs_5_0 ps_5_0, D3D11)"
+        )
+        .software
+    );
+}
+
+#[test]
+fn test_parse_masked() {
+    assert_eq!(GpuIdentity::parse("WebKit WebGL"), GpuIdentity::default());
+    assert_eq!(GpuIdentity::parse(""), GpuIdentity::default());
+}
+
+#[test]
+fn test_plausibility() {
+    let parameters = WebGLParametersFingerPrint {
+        max_texture_size: 16384,
+        ..Default::default()
+    };
+    let rtx = GpuIdentity::parse(NVIDIA_D3D11_RENDERER);
+    assert!(rtx.check_plausibility(&parameters).is_empty());
+
+    let spoofed = WebGLParametersFingerPrint {
+        max_texture_size: 4096,
+        ..Default::default()
+    };
+    assert!(!rtx.check_plausibility(&spoofed).is_empty());
+
+    let odd = WebGLParametersFingerPrint {
+        max_texture_size: 10000,
+        ..Default::default()
+    };
+    assert!(!GpuIdentity::parse("Apple GPU")
+        .check_plausibility(&odd)
+        .is_empty());
+}
@@ -0,0 +1,55 @@
+use fingerprint_rs::*;
+
+#[test]
+fn test_likely_private_mode() {
+    let normal = StorageFingerPrint {
+        quota_mb: Some(131072),
+        js_heap_size_limit_mb: Some(4096),
+        indexed_db_write: Some(true),
+        opfs_write: Some(true),
+        ..Default::default()
+    };
+    assert!(!normal.likely_private_mode());
+
+    let chrome_incognito = StorageFingerPrint {
+        quota_mb: Some(2048),
+        ..normal.clone()
+    };
+    assert!(chrome_incognito.likely_private_mode());
+
+    let safari_private = StorageFingerPrint {
+        quota_mb: None,
+        js_heap_size_limit_mb: None,
+        opfs_write: Some(false),
+        ..normal.clone()
+    };
+    assert!(safari_private.likely_private_mode());
+
+    // a browser without OPFS or a quota estimate isn't private, we just can't tell.
+    let old_browser = StorageFingerPrint {
+        indexed_db_write: Some(true),
+        ..Default::default()
+    };
+    assert!(!old_browser.likely_private_mode());
+}
+
+#[test]
+fn test_likely_private_mode_buckets() {
+    // twice a 3000MiB heap limit is 6000MiB, which buckets down to 4096.
+    let quota = |quota_mb| StorageFingerPrint {
+        quota_mb: Some(quota_mb),
+        js_heap_size_limit_mb: Some(3000),
+        ..Default::default()
+    };
+    assert!(quota(2048).likely_private_mode());
+    // a raw quota of 5000MiB is below 6000 but lands in the same bucket as the threshold.
+    assert!(!quota(4096).likely_private_mode());
+    assert!(!quota(8192).likely_private_mode());
+
+    let legacy = |quota_mb| StorageFingerPrint {
+        quota_mb: Some(quota_mb),
+        ..Default::default()
+    };
+    assert!(legacy(64).likely_private_mode());
+    assert!(!legacy(128).likely_private_mode());
+}
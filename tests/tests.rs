@@ -1,8 +1,11 @@
 use fingerprint_rs::*;
+use js_sys::Reflect;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_test::wasm_bindgen_test_configure;
 use wasm_bindgen_test::*;
-use web_sys::{window, HtmlCanvasElement, WebGl2RenderingContext};
+use web_sys::{
+    window, HtmlCanvasElement, PermissionState, WebGl2RenderingContext, WebGlRenderingContext,
+};
 wasm_bindgen_test_configure!(run_in_browser);
 
 #[wasm_bindgen_test]
@@ -46,6 +49,9 @@ async fn test_audio_formats() {
 #[wasm_bindgen_test]
 async fn test_navigator() {
     let nav_finger_print = NavigatorFingerPrint::new(window().unwrap().navigator()).await;
+    assert!(nav_finger_print
+        .as_ref()
+        .is_some_and(|n| n.cookie_enabled.is_some() && n.webdriver.is_some()));
     console_log!("{nav_finger_print:#?}");
 }
 
@@ -61,6 +67,16 @@ async fn test_canvas() {
 #[wasm_bindgen_test]
 async fn test_webgl() {
     let webgl_fingerprint = WebGLFingerPrint::new(&window().unwrap()).unwrap();
+    assert!(webgl_fingerprint.primary().is_some());
+    console_log!("{webgl_fingerprint:#?}");
+    console_log!("{:#?}", webgl_fingerprint.context_differences());
+}
+
+#[wasm_bindgen_test]
+async fn test_webgl1() {
+    let webgl_fingerprint =
+        WebGLContextFingerPrint::new::<WebGlRenderingContext>(&window().unwrap());
+    assert!(webgl_fingerprint.is_some());
     console_log!("{webgl_fingerprint:#?}");
 }
 
@@ -105,7 +121,15 @@ async fn test_shader_precision() {
             .unwrap()
             .unchecked_into::<WebGl2RenderingContext>(),
     );
-    assert!(shader.is_some());
+    let shader = shader.unwrap();
+    assert_eq!(
+        shader.format(
+            WebGl2RenderingContext::FRAGMENT_SHADER,
+            WebGl2RenderingContext::MEDIUM_FLOAT
+        ),
+        Some(shader.fragment().medium_float)
+    );
+    assert!(shader.least_min() <= shader.most_max());
     console_log!("{shader:#?}");
 }
 
@@ -127,3 +151,277 @@ async fn test_webgl_parameters() {
     assert!(fp.is_some());
     console_log!("{fp:#?}");
 }
+
+#[wasm_bindgen_test]
+async fn test_eme() {
+    let eme = EmeFingerPrint::new(&window().unwrap().navigator()).await;
+    assert!(eme.is_some());
+    console_log!("{eme:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_speech() {
+    let speech = SpeechFingerPrint::new(&window().unwrap()).await;
+    assert!(speech.is_some());
+    console_log!("{speech:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_webgl2_parameters() {
+    let fp = WebGL2ParametersFingerPrint::new(
+        &window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("canvas")
+            .unwrap()
+            .unchecked_into::<HtmlCanvasElement>()
+            .get_context("webgl2")
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<WebGl2RenderingContext>(),
+    );
+    assert!(fp.is_some());
+    console_log!("{fp:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_webgl_rendering() {
+    let gl = || {
+        window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("canvas")
+            .unwrap()
+            .unchecked_into::<HtmlCanvasElement>()
+            .get_context("webgl2")
+            .unwrap()
+            .unwrap()
+            .unchecked_into::<WebGl2RenderingContext>()
+    };
+    let rendering = WebGLRenderingFingerPrint::new(&gl()).unwrap();
+    let rendering_2 = WebGLRenderingFingerPrint::new(&gl()).unwrap();
+    // every scene has to render the same on a fresh context, or it's useless as a fingerprint.
+    assert_eq!(rendering, rendering_2);
+    assert!(rendering.gradient_highp_hash.is_some());
+    assert!(rendering.texture_hash.is_some());
+    assert!(rendering.blending_hash.is_some());
+    console_log!("{rendering:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_webgpu() {
+    // headless test browsers usually have no adapter, so Unsupported is a valid result here.
+    let webgpu = WebGpuFingerPrint::new(&window().unwrap().navigator()).await;
+    if let Some(webgpu) = webgpu.supported() {
+        assert!(!webgpu.limits.is_empty());
+    }
+    console_log!("{webgpu:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_media_queries() {
+    let window = window().unwrap();
+    let media_queries = MediaQueryFingerPrint::new(&window).unwrap();
+    // every browser we support knows prefers-color-scheme and color-gamut.
+    assert!(media_queries.prefers_color_scheme.is_some());
+    assert!(media_queries.color_gamut.is_some());
+    let screen = ScreenFingerPrint::new(&window.screen().unwrap()).unwrap();
+    console_log!("{media_queries:#?}");
+    console_log!(
+        "color gamut matches screen: {:?}",
+        media_queries.color_gamut_matches_screen(&screen)
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_window_geometry() {
+    let window = window().unwrap();
+    let screen = ScreenFingerPrint::new(&window.screen().unwrap()).unwrap();
+    let geometry = WindowGeometryFingerPrint::new(&window, &screen);
+    assert!(geometry.is_some());
+    let geometry = geometry.unwrap();
+    console_log!("{geometry:#?}");
+    let engine = FeatureDetectionFingerPrint::new().detect().engine;
+    console_log!(
+        "zoom: {:?}, letterboxed: {}, insets: {:?}",
+        geometry.zoom(engine),
+        geometry.letterboxed(),
+        ScreenInsets::new(&screen)
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_multi_screen() {
+    let window = window().unwrap();
+    let screen = ScreenFingerPrint::new(&window.screen().unwrap()).unwrap();
+    let has_api = Reflect::has(&window, &"getScreenDetails".into()).unwrap();
+    let permission = window_management_permission(&window).await;
+    assert_eq!(permission.is_some(), has_api);
+    assert_eq!(screen.is_extended.is_some(), has_api);
+    // None unless the test browser was started with window-management granted.
+    let screens = ScreenDetailFingerPrint::all(&window).await;
+    assert_eq!(
+        screens.is_some(),
+        permission == Some(PermissionState::Granted)
+    );
+    console_log!(
+        "is_extended: {:?}, screens: {screens:#?}",
+        screen.is_extended
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_client_hints() {
+    let navigator = window().unwrap().navigator();
+    let client_hints = ClientHintsFingerPrint::new(&navigator).await;
+    // userAgentData is chromium only.
+    let chromium = navigator.user_agent().unwrap().contains("Chrome/");
+    assert_eq!(client_hints.supported().is_some(), chromium);
+    console_log!("{client_hints:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_engine_detection() {
+    let features = FeatureDetectionFingerPrint::new();
+    let detection = features.detect();
+    assert_ne!(detection.engine, Engine::Unknown);
+    let user_agent = UserAgent::parse(&window().unwrap().navigator().user_agent().unwrap());
+    assert_eq!(detection.matches_user_agent(&user_agent), Some(true));
+    console_log!("{detection}, present: {:?}", features.present);
+}
+
+#[wasm_bindgen_test]
+async fn test_intl() {
+    let intl = IntlFingerPrint::new();
+    assert!(intl.is_some());
+    let intl = intl.unwrap();
+    assert!(intl.time_zone.is_some());
+    assert!(intl.number_format.is_some());
+    console_log!("{intl:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_intl_support() {
+    let support = IntlSupportFingerPrint::new();
+    assert!(support.is_some());
+    let support = support.unwrap();
+    // every browser with Intl supports en-US.
+    assert!(support
+        .supported_locales
+        .iter()
+        .all(|s| s.locales.contains(&"en-US".to_string())));
+    for values in &support.supported_values {
+        console_log!(
+            "{}: {} values, {}",
+            values.key,
+            values.values.len(),
+            values.hash
+        );
+    }
+    console_log!("{:#?}", support.supported_locales);
+}
+
+#[wasm_bindgen_test]
+async fn test_math() {
+    let math = MathFingerPrint::new();
+    assert_eq!(math.results.len(), MATH_BATTERY.len());
+    assert_eq!(math, MathFingerPrint::new());
+    console_log!("{math:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_js_quirks() {
+    let quirks = JsQuirksFingerPrint::new();
+    assert_ne!(quirks.error_stack_format, StackFormat::Unknown);
+    assert!(quirks.eval_to_string_length.is_some());
+    assert!(quirks.sort_signature.is_some());
+    // every engine we run the tests on implements the ES2019 stable sort.
+    assert_eq!(quirks.sort_stable, vec![true; SORT_STABILITY_LENGTHS.len()]);
+    assert_eq!(quirks.to_fixed.len(), TO_FIXED_CASES.len());
+    assert_eq!(
+        quirks.prototype_key_orders.len(),
+        PROTOTYPE_KEY_OBJECTS.len()
+    );
+    console_log!("{quirks:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_property_names() {
+    let property_names = PropertyNamesFingerPrint::new(&window().unwrap());
+    assert!(property_names.is_some());
+    let property_names = property_names.unwrap();
+    assert!(property_names
+        .navigator
+        .names
+        .contains(&"userAgent".to_string()));
+    // the test harness defines its own globals, so window may have additions, but nothing should be missing.
+    assert!(property_names.window.missing.is_empty());
+    console_log!("navigator added: {:?}", property_names.navigator.added);
+    console_log!("window added: {:?}", property_names.window.added);
+}
+
+#[wasm_bindgen_test]
+async fn test_web_apis() {
+    let web_apis = WebApiFingerPrint::new(&window().unwrap()).await;
+    assert_eq!(web_apis.apis.len(), WEB_APIS.len());
+    console_log!("{web_apis:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_storage() {
+    let storage = StorageFingerPrint::new(&window().unwrap()).await;
+    assert_eq!(storage.local_storage_write, Some(true));
+    assert_eq!(storage.indexed_db_write, Some(true));
+    console_log!(
+        "{storage:#?}, likely private mode: {}",
+        storage.likely_private_mode()
+    );
+}
+
+#[wasm_bindgen_test]
+async fn test_bot_signals() {
+    let window = window().unwrap();
+    let webgl = WebGLFingerPrint::new(&window);
+    let bot_signals = BotSignals::new(&window, webgl.as_ref()).await;
+    assert!(bot_signals.is_some());
+    let bot_signals = bot_signals.unwrap();
+    if bot_signals.webdriver {
+        // headless test runs drive the browser through webdriver.
+        assert_eq!(bot_signals.verdict(), BotVerdict::Bot);
+    } else {
+        // a browser opened by hand with NO_HEADLESS=1.
+        assert!(
+            bot_signals.automation_globals.is_empty(),
+            "{bot_signals:#?}"
+        );
+        assert!(
+            bot_signals.automation_stack_markers.is_empty(),
+            "{bot_signals:#?}"
+        );
+        assert!(!bot_signals.headless_user_agent);
+        assert!(!bot_signals.notification_permission_mismatch);
+        assert!(!bot_signals.missing_window_chrome);
+        assert_eq!(bot_signals.verdict(), BotVerdict::Human, "{bot_signals:#?}");
+    }
+    console_log!("{bot_signals:#?}, verdict: {:?}", bot_signals.verdict());
+}
+
+#[wasm_bindgen_test]
+async fn test_tamper() {
+    let tamper = TamperFingerPrint::new();
+    assert!(!tamper.to_string_tampered);
+    assert!(tamper.untrusted_fields().is_empty(), "{tamper:#?}");
+}
+
+#[wasm_bindgen_test]
+async fn test_inconsistencies() {
+    let finger_print = FingerPrint::new().await.unwrap();
+    let inconsistencies = finger_print.inconsistencies();
+    assert!(
+        inconsistencies.iter().all(|i| i.severity != Severity::High),
+        "{inconsistencies:#?}"
+    );
+    console_log!("{inconsistencies:#?}");
+}
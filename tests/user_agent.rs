@@ -0,0 +1,206 @@
+mod common;
+
+use common::*;
+use fingerprint_rs::*;
+
+fn assert_ua(
+    ua: &str,
+    browser: (BrowserFamily, Option<&str>),
+    engine: (Engine, Option<&str>),
+    os: (OsFamily, Option<&str>),
+    device: DeviceClass,
+) {
+    let parsed = UserAgent::parse(ua);
+    assert_eq!(
+        (parsed.browser, parsed.browser_version.as_deref()),
+        browser,
+        "{ua}"
+    );
+    assert_eq!(
+        (parsed.engine, parsed.engine_version.as_deref()),
+        engine,
+        "{ua}"
+    );
+    assert_eq!((parsed.os, parsed.os_version.as_deref()), os, "{ua}");
+    assert_eq!(parsed.device, device, "{ua}");
+    assert!(!parsed.bot, "{ua}");
+}
+
+#[test]
+fn test_parse_chrome_windows() {
+    assert_ua(
+        WINDOWS_CHROME,
+        (BrowserFamily::Chrome, Some("120.0.0.0")),
+        (Engine::Blink, Some("120.0.0.0")),
+        (OsFamily::Windows, Some("10")),
+        DeviceClass::Desktop,
+    );
+}
+
+#[test]
+fn test_parse_edge() {
+    assert_ua(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36 Edg/120.0.2210.91",
+        (BrowserFamily::Edge, Some("120.0.2210.91")),
+        (Engine::Blink, Some("120.0.0.0")),
+        (OsFamily::Windows, Some("10")),
+        DeviceClass::Desktop,
+    );
+    assert_ua(
+        "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/70.0.3538.102 Safari/537.36 Edge/18.19045",
+        (BrowserFamily::Edge, Some("18.19045")),
+        (Engine::EdgeHTML, Some("18.19045")),
+        (OsFamily::Windows, Some("10")),
+        DeviceClass::Desktop,
+    );
+}
+
+#[test]
+fn test_parse_firefox() {
+    assert_ua(
+        "Mozilla/5.0 (Windows NT 6.1; Win64; x64; rv:115.0) Gecko/20100101 Firefox/115.0",
+        (BrowserFamily::Firefox, Some("115.0")),
+        (Engine::Gecko, Some("115.0")),
+        (OsFamily::Windows, Some("7")),
+        DeviceClass::Desktop,
+    );
+    assert_ua(
+        "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:121.0) Gecko/20100101 Firefox/121.0",
+        (BrowserFamily::Firefox, Some("121.0")),
+        (Engine::Gecko, Some("121.0")),
+        (OsFamily::Linux, None),
+        DeviceClass::Desktop,
+    );
+    assert_ua(
+        "Mozilla/5.0 (Android 14; Mobile; rv:121.0) Gecko/121.0 Firefox/121.0",
+        (BrowserFamily::Firefox, Some("121.0")),
+        (Engine::Gecko, Some("121.0")),
+        (OsFamily::Android, Some("14")),
+        DeviceClass::Mobile,
+    );
+}
+
+#[test]
+fn test_parse_safari() {
+    assert_ua(
+        MAC_SAFARI,
+        (BrowserFamily::Safari, Some("17.2")),
+        (Engine::WebKit, Some("605.1.15")),
+        (OsFamily::MacOs, Some("10.15.7")),
+        DeviceClass::Desktop,
+    );
+    assert_ua(
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1.2 Mobile/15E148 Safari/604.1",
+        (BrowserFamily::Safari, Some("17.1.2")),
+        (Engine::WebKit, Some("605.1.15")),
+        (OsFamily::Ios, Some("17.1.2")),
+        DeviceClass::Mobile,
+    );
+    assert_ua(
+        "Mozilla/5.0 (iPad; CPU OS 16_6 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/16.6 Mobile/15E148 Safari/604.1",
+        (BrowserFamily::Safari, Some("16.6")),
+        (Engine::WebKit, Some("605.1.15")),
+        (OsFamily::Ios, Some("16.6")),
+        DeviceClass::Tablet,
+    );
+}
+
+#[test]
+fn test_parse_ios_forks_are_webkit() {
+    assert_ua(
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) CriOS/120.0.6099.119 Mobile/15E148 Safari/604.1",
+        (BrowserFamily::Chrome, Some("120.0.6099.119")),
+        (Engine::WebKit, Some("605.1.15")),
+        (OsFamily::Ios, Some("17.2")),
+        DeviceClass::Mobile,
+    );
+    assert_ua(
+        "Mozilla/5.0 (iPhone; CPU iPhone OS 17_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) FxiOS/121.0 Mobile/15E148 Safari/605.1.15",
+        (BrowserFamily::Firefox, Some("121.0")),
+        (Engine::WebKit, Some("605.1.15")),
+        (OsFamily::Ios, Some("17.2")),
+        DeviceClass::Mobile,
+    );
+}
+
+#[test]
+fn test_parse_android_chromium_forks() {
+    assert_ua(
+        ANDROID_CHROME,
+        (BrowserFamily::Chrome, Some("120.0.0.0")),
+        (Engine::Blink, Some("120.0.0.0")),
+        (OsFamily::Android, Some("10")),
+        DeviceClass::Mobile,
+    );
+    assert_ua(
+        "Mozilla/5.0 (Linux; Android 13; SM-X700) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/23.0 Chrome/115.0.0.0 Safari/537.36",
+        (BrowserFamily::SamsungInternet, Some("23.0")),
+        (Engine::Blink, Some("115.0.0.0")),
+        (OsFamily::Android, Some("13")),
+        DeviceClass::Tablet,
+    );
+    assert_ua(
+        "Mozilla/5.0 (Linux; Android 13; CUBOT X70) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/119.0.0.0 Mobile Safari/537.36 OPR/79.0.4195.76783",
+        (BrowserFamily::Opera, Some("79.0.4195.76783")),
+        (Engine::Blink, Some("119.0.0.0")),
+        (OsFamily::Android, Some("13")),
+        DeviceClass::Mobile,
+    );
+}
+
+#[test]
+fn test_parse_chrome_os_and_ie() {
+    assert_ua(
+        "Mozilla/5.0 (X11; CrOS x86_64 14541.0.0) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36",
+        (BrowserFamily::Chrome, Some("120.0.0.0")),
+        (Engine::Blink, Some("120.0.0.0")),
+        (OsFamily::ChromeOs, Some("14541.0.0")),
+        DeviceClass::Desktop,
+    );
+    assert_ua(
+        "Mozilla/5.0 (Windows NT 6.3; Trident/7.0; rv:11.0) like Gecko",
+        (BrowserFamily::InternetExplorer, Some("11.0")),
+        (Engine::Trident, Some("7.0")),
+        (OsFamily::Windows, Some("8.1")),
+        DeviceClass::Desktop,
+    );
+}
+
+#[test]
+fn test_parse_bots() {
+    for ua in [
+        "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+        "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)",
+        "Mozilla/5.0 (compatible; Baiduspider/2.0; +http://www.baidu.com/search/spider.html)",
+        "Mozilla/5.0 (compatible; Yahoo! Slurp; http://help.yahoo.com/help/us/ysearch/slurp)",
+        "facebookexternalhit/1.1 (+http://www.facebook.com/externalhit_uatext.php)",
+        "curl/8.4.0",
+        "python-requests/2.31.0",
+    ] {
+        assert!(UserAgent::parse(ua).bot, "{ua}");
+    }
+    let headless = UserAgent::parse(HEADLESS_CHROME);
+    assert!(headless.headless);
+    assert!(!headless.bot);
+    assert_eq!(headless.browser, BrowserFamily::Chrome);
+    assert_eq!(headless.browser_version.as_deref(), Some("120.0.6099.109"));
+}
+
+#[test]
+fn test_parse_webview() {
+    for ua in [
+        ANDROID_WEBVIEW,
+        "Mozilla/5.0 (Linux; Android 4.4.4; Nexus 5 Build/KTU84P) AppleWebKit/537.36 (KHTML, like Gecko) Version/4.0 Chrome/33.0.0.0 Mobile Safari/537.36",
+    ] {
+        let parsed = UserAgent::parse(ua);
+        assert!(parsed.webview, "{ua}");
+        assert_eq!(parsed.engine, Engine::Blink, "{ua}");
+    }
+    for ua in [
+        "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Mobile Safari/537.36",
+        "Mozilla/5.0 (Linux; Android 14; SM-S918B) AppleWebKit/537.36 (KHTML, like Gecko) SamsungBrowser/24.0 Chrome/117.0.0.0 Mobile Safari/537.36",
+        WINDOWS_CHROME,
+    ] {
+        assert!(!UserAgent::parse(ua).webview, "{ua}");
+    }
+}
@@ -0,0 +1,37 @@
+use fingerprint_rs::*;
+
+fn context(version: &str, shading_language_version: &str) -> WebGLContextFingerPrint {
+    WebGLContextFingerPrint {
+        renderer: Some(
+            "ANGLE (Intel, Intel(R) UHD Graphics 620 Direct3D11 vs_5_0 ps_5_0, D3D11)".to_string(),
+        ),
+        parameters: Some(WebGLParametersFingerPrint {
+            max_texture_size: 16384,
+            version: version.to_string(),
+            shading_language_version: shading_language_version.to_string(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_context_differences_ignore_versions() {
+    let webgl = WebGLFingerPrint {
+        webgl: Some(context(
+            "WebGL 1.0 (OpenGL ES 2.0 Chromium)",
+            "WebGL GLSL ES 1.0 (OpenGL ES GLSL ES 1.0 Chromium)",
+        )),
+        webgl2: Some(context(
+            "WebGL 2.0 (OpenGL ES 3.0 Chromium)",
+            "WebGL GLSL ES 3.00 (OpenGL ES GLSL ES 3.0 Chromium)",
+        )),
+    };
+    assert_eq!(webgl.context_differences(), Vec::<&str>::new());
+
+    let mut spoofed = webgl.clone();
+    if let Some(parameters) = spoofed.webgl.as_mut().and_then(|c| c.parameters.as_mut()) {
+        parameters.max_texture_size = 8192;
+    }
+    assert_eq!(spoofed.context_differences(), vec!["parameters"]);
+}
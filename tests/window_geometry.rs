@@ -0,0 +1,80 @@
+use fingerprint_rs::*;
+
+fn geometry(inner: (i32, i32), outer: (i32, i32)) -> WindowGeometryFingerPrint {
+    WindowGeometryFingerPrint {
+        inner_width: inner.0,
+        inner_height: inner.1,
+        outer_width: outer.0,
+        outer_height: outer.1,
+        device_pixel_ratio: 1.0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_zoom() {
+    let zoom = |inner, outer| geometry(inner, outer).zoom(Engine::Blink);
+    assert_eq!(zoom((1920, 969), (1920, 1040)), Some(1.0));
+    assert_eq!(zoom((1536, 775), (1920, 1040)), Some(1.25));
+    assert_eq!(zoom((2400, 1211), (1920, 1040)), Some(0.8));
+    // devtools docked to the side
+    assert_eq!(zoom((1200, 969), (1920, 1040)), None);
+    assert_eq!(zoom((0, 0), (1920, 1040)), None);
+}
+
+#[test]
+fn test_zoom_other_engines() {
+    // firefox at 125% zooms the outer width along with the inner one.
+    let gecko = geometry((1536, 775), (1552, 832));
+    assert_eq!(gecko.zoom(Engine::Gecko), None);
+    // the chrome shaped geometry doesn't count without knowing it's chrome.
+    let chrome = geometry((1536, 775), (1920, 1040));
+    assert_eq!(chrome.zoom(Engine::Gecko), None);
+    assert_eq!(chrome.zoom(Engine::WebKit), None);
+    assert_eq!(chrome.zoom(Engine::Unknown), None);
+}
+
+#[test]
+fn test_letterboxed() {
+    assert!(geometry((1000, 900), (1016, 1000)).letterboxed());
+    assert!(geometry((1400, 800), (1416, 900)).letterboxed());
+    assert!(!geometry((1920, 969), (1920, 1040)).letterboxed());
+    assert!(!geometry((1000, 969), (1016, 1040)).letterboxed());
+}
+
+#[test]
+fn test_screen_insets() {
+    // windows, taskbar at the bottom
+    let windows = ScreenFingerPrint {
+        width: 1920,
+        height: 1080,
+        avail_width: 1920,
+        avail_height: 1040,
+        ..Default::default()
+    };
+    assert_eq!(
+        ScreenInsets::new(&windows),
+        ScreenInsets {
+            bottom: 40,
+            ..Default::default()
+        }
+    );
+    // mac, menu bar at the top and the dock on the left
+    let mac = ScreenFingerPrint {
+        width: 1440,
+        height: 900,
+        avail_width: 1376,
+        avail_height: 875,
+        avail_top: 25,
+        avail_left: 64,
+        ..Default::default()
+    };
+    assert_eq!(
+        ScreenInsets::new(&mac),
+        ScreenInsets {
+            top: 25,
+            left: 64,
+            ..Default::default()
+        }
+    );
+}